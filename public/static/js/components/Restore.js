@@ -111,9 +111,33 @@ define(["knockout", "reqwest", "Storage", "Pattern"], function(ko, reqwest, Stor
 
 		if (databaseName !== undefined) {
 			this.databaseName(databaseName);
+		} else if (nonEmptyString(backupPath)) {
+			this.suggestDatabaseName(backupPath);
 		}
 	};
 
+	Restore.prototype.suggestDatabaseName = function(backupPath) {
+		reqwest({
+			url: "/api/v3/suggest",
+			type: "json",
+			method: "POST",
+			contentType: "application/json",
+			data: JSON.stringify({
+				path: backupPath,
+			}),
+		}).then(
+			function(resp) {
+				if (
+					resp.success &&
+					resp.result.database_name !== null &&
+					this.backup() === backupPath
+				) {
+					this.databaseName(resp.result.database_name);
+				}
+			}.bind(this)
+		);
+	};
+
 	Restore.prototype.backupToCall = function() {
 		const result = {};
 
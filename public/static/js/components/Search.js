@@ -1,6 +1,27 @@
 "use strict";
 
-define(["knockout", "reqwest"], function(ko, reqwest) {
+define(["knockout", "reqwest", "moment"], function(ko, reqwest, moment) {
+	const DATE_FORMAT = "YYYY-MM-DD HH:mm:ss";
+	const SIZE_UNITS = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+	const formatSize = function(size) {
+		let unit = 0;
+
+		while (size >= 1024 && unit < SIZE_UNITS.length - 1) {
+			size /= 1024;
+			unit += 1;
+		}
+
+		return size.toFixed(unit === 0 ? 0 : 1) + " " + SIZE_UNITS[unit];
+	};
+
+	const SearchResult = function(params) {
+		this.path = params.path;
+		this.format = params.format;
+		this.size = formatSize(params.size);
+		this.date = moment.unix(params.dump_timestamp || params.modified).format(DATE_FORMAT);
+	};
+
 	const Restore = function(params) {
 		this.callback = params.callback;
 
@@ -15,7 +36,7 @@ define(["knockout", "reqwest"], function(ko, reqwest) {
 		}, this);
 
 		this.selectResult = function(value) {
-			this.callback(value);
+			this.callback(value.path);
 		}.bind(this);
 	};
 
@@ -33,7 +54,11 @@ define(["knockout", "reqwest"], function(ko, reqwest) {
 				function(resp) {
 					if (resp.success) {
 						this.isError(false);
-						this.results(resp.result);
+						this.results(
+							resp.result.map(function(item) {
+								return new SearchResult(item);
+							})
+						);
 					} else {
 						this.isError(true);
 						this.errorMessage(resp.message);
@@ -0,0 +1,201 @@
+use super::ConfigError;
+use super::ConfigResult;
+use std::env;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs;
+use std::mem;
+
+/// Secret value given inline or as reference resolved when configuration is loaded. Value is
+/// never written by `Debug`.
+#[derive(Clone, Deserialize)]
+#[serde(from = "SecretSource")]
+pub struct Secret {
+    source: SecretSource,
+    value: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum SecretSource {
+    Plain(String),
+    Env { env: String },
+    File { file: String },
+    Pgpass { pgpass: String },
+}
+
+impl Secret {
+    /// Returns resolved value, empty if secret is not resolved.
+    pub fn value(&self) -> &str {
+        self.value.as_deref().unwrap_or_default()
+    }
+
+    /// Reads referenced value, connection parameters are used to find password in `.pgpass`
+    /// file.
+    pub fn resolve(
+        &mut self,
+        host: &str,
+        port: u16,
+        database: &str,
+        role: &str,
+    ) -> ConfigResult<()> {
+        let value = match self.source {
+            SecretSource::Pgpass { ref pgpass } => {
+                let content = fs::read_to_string(pgpass).map_err(|err| {
+                    ConfigError::format(format_args!("Failed to read {} - {}", pgpass, err))
+                })?;
+
+                find_pgpass(&content, &[host, &format!("{}", port), database, role]).ok_or_else(
+                    || {
+                        ConfigError::format(format_args!(
+                            "Password of {}@{}:{} not found in {}",
+                            role, host, port, pgpass
+                        ))
+                    },
+                )?
+            }
+            ref source => read(source)?,
+        };
+
+        self.value = Some(value);
+
+        Ok(())
+    }
+
+    /// Reads referenced value of secret which is not a database password, `.pgpass` reference
+    /// is rejected.
+    pub fn resolve_value(&mut self) -> ConfigResult<()> {
+        self.value = Some(read(&self.source)?);
+
+        Ok(())
+    }
+}
+
+/// Reads value of secret which does not depend on connection parameters.
+fn read(source: &SecretSource) -> ConfigResult<String> {
+    match source {
+        SecretSource::Plain(value) => Ok(value.clone()),
+        SecretSource::Pgpass { pgpass } => Err(ConfigError::format(format_args!(
+            "{} can be used only for destination passwords",
+            pgpass
+        ))),
+        SecretSource::Env { env } => env::var(env).map_err(|_| {
+            ConfigError::format(format_args!("Environment variable {} is not set", env))
+        }),
+        SecretSource::File { file } => Ok(fs::read_to_string(file)
+            .map_err(|err| {
+                ConfigError::format(format_args!("Failed to read secret {} - {}", file, err))
+            })?
+            .trim_end_matches(&['\r', '\n'][..])
+            .to_string()),
+    }
+}
+
+impl From<SecretSource> for Secret {
+    fn from(source: SecretSource) -> Secret {
+        let value = match source {
+            SecretSource::Plain(ref value) => Some(value.clone()),
+            _ => None,
+        };
+
+        Secret { source, value }
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.source {
+            SecretSource::Plain(_) => write!(f, "Secret(***)"),
+            ref source => write!(f, "Secret({:?})", source),
+        }
+    }
+}
+
+/// Returns password of first matching line of `.pgpass` file, fields are
+/// `hostname:port:database:username:password` where `*` matches any value.
+fn find_pgpass(content: &str, expected: &[&str; 4]) -> Option<String> {
+    content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(split_pgpass)
+        .filter(|fields| fields.len() == 5)
+        .find(|fields| {
+            expected
+                .iter()
+                .zip(fields)
+                .all(|(expected, field)| field == "*" || field == expected)
+        })
+        .map(|mut fields| fields.remove(4))
+}
+
+/// Splits line by colons, `\:` and `\\` are escaped colon and backslash.
+fn split_pgpass(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => field.extend(chars.next()),
+            ':' => fields.push(mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_pgpass;
+    use super::Secret;
+
+    const KEY: [&str; 4] = ["db.example.com", "5432", "postgres", "restore"];
+
+    #[test]
+    fn find_pgpass_shold_match_first_line() {
+        let content = "# comment\n\
+                       other.example.com:*:*:restore:wrong\n\
+                       db.example.com:5432:*:restore:pa\\:ss\\\\\n\
+                       *:*:*:*:fallback\n";
+
+        assert_eq!(Some("pa:ss\\".to_string()), find_pgpass(content, &KEY));
+        assert_eq!(
+            Some("fallback".to_string()),
+            find_pgpass(content, &["db.example.com", "5433", "postgres", "restore"])
+        );
+        assert_eq!(None, find_pgpass("db.example.com:5432:sales:*:x", &KEY));
+    }
+
+    #[test]
+    fn debug_shold_not_show_value() {
+        let mut plain: Secret = serde_yaml::from_str("pass").unwrap();
+        let mut env: Secret = serde_yaml::from_str("{env: PGRESTORE_TEST_SECRET}").unwrap();
+
+        std::env::set_var("PGRESTORE_TEST_SECRET", "hidden");
+        plain
+            .resolve("localhost", 5432, "postgres", "postgres")
+            .unwrap();
+        env.resolve("localhost", 5432, "postgres", "postgres")
+            .unwrap();
+
+        assert_eq!("pass", plain.value());
+        assert_eq!("hidden", env.value());
+        assert_eq!("Secret(***)", format!("{:?}", plain));
+        assert!(!format!("{:?}", env).contains("hidden"));
+    }
+
+    #[test]
+    fn resolve_value_shold_reject_pgpass() {
+        let mut plain: Secret = serde_yaml::from_str("http://proxy:3128").unwrap();
+        let mut pgpass: Secret = serde_yaml::from_str("{pgpass: /etc/.pgpass}").unwrap();
+
+        plain.resolve_value().unwrap();
+
+        assert_eq!("http://proxy:3128", plain.value());
+        assert!(pgpass.resolve_value().is_err());
+    }
+}
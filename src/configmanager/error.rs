@@ -0,0 +1,33 @@
+use crate::config::ConfigError;
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+pub type ConfigManagerResult<T> = Result<T, ConfigManagerError>;
+
+#[derive(Debug)]
+pub struct ConfigManagerError {
+    message: String,
+}
+
+impl ConfigManagerError {
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn config_error(error: ConfigError) -> ConfigManagerError {
+        ConfigManagerError {
+            message: format!("{}", error),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for ConfigManagerError {}
+
+impl Display for ConfigManagerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,69 @@
+mod error;
+mod reloader;
+mod signal;
+
+pub use self::error::ConfigManagerError;
+pub use self::error::ConfigManagerResult;
+pub use self::reloader::Reloader;
+pub use self::signal::block_reload_signal;
+pub use self::signal::start;
+
+use crate::config;
+use crate::config::ConfigRef;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+
+/// Holds current configuration, replaced as a whole when configuration is reloaded.
+#[derive(Debug, Clone)]
+pub struct ConfigManagerRef {
+    inner: Arc<RwLock<ConfigManager>>,
+}
+
+impl ConfigManagerRef {
+    // Lock is held only to clone or replace reference, so poisoned lock still holds valid
+    // configuration.
+    fn read(&self) -> RwLockReadGuard<'_, ConfigManager> {
+        self.inner.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns current configuration, jobs keep configuration they are started with.
+    pub fn get(&self) -> ConfigRef {
+        self.read().config.clone()
+    }
+
+    /// Loads and validates configuration file, replaces current configuration only if new
+    /// one is valid.
+    pub fn reload(&self) -> ConfigManagerResult<ConfigRef> {
+        let path = self.read().path.clone();
+        let config = config::load(&path).map_err(ConfigManagerError::config_error)?;
+
+        config::validate(config.clone()).map_err(ConfigManagerError::config_error)?;
+
+        self.inner
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .config = config.clone();
+
+        info!("Configuration reloaded from {}", path.display());
+
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+struct ConfigManager {
+    path: PathBuf,
+    config: ConfigRef,
+}
+
+pub fn create(path: &Path, config: ConfigRef) -> ConfigManagerRef {
+    ConfigManagerRef {
+        inner: Arc::new(RwLock::new(ConfigManager {
+            path: path.into(),
+            config,
+        })),
+    }
+}
@@ -0,0 +1,76 @@
+use super::ConfigManagerRef;
+use super::ConfigManagerResult;
+use crate::config::ConfigRef;
+use crate::healthmanager;
+use crate::healthmanager::HealthManagerRef;
+use crate::pathmanager::NamePatterns;
+use crate::pathmanager::PathManagerRef;
+use crate::scanmanager::ScanManagerRef;
+use crate::scheduler::SchedulerRef;
+use crate::worker;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Reloads configuration and applies it to search directories, name patterns, schedules and
+/// destination checks, shared by HTTP handler and SIGHUP handler.
+#[derive(Debug, Clone)]
+pub struct Reloader {
+    config_manager: ConfigManagerRef,
+    path_manager: PathManagerRef,
+    scan_manager: ScanManagerRef,
+    scheduler: SchedulerRef,
+    health_manager: HealthManagerRef,
+    lock: Arc<Mutex<()>>,
+}
+
+impl Reloader {
+    pub fn new(
+        config_manager: ConfigManagerRef,
+        path_manager: PathManagerRef,
+        scan_manager: ScanManagerRef,
+        scheduler: SchedulerRef,
+        health_manager: HealthManagerRef,
+    ) -> Reloader {
+        Reloader {
+            config_manager,
+            path_manager,
+            scan_manager,
+            scheduler,
+            health_manager,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Replaces current configuration, returns new configuration. Running jobs keep
+    /// configuration they are started with.
+    pub fn reload(&self) -> ConfigManagerResult<ConfigRef> {
+        // Reloads are serialized, so components are updated in the same order as configuration.
+        let _lock = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+        let config = self.config_manager.reload()?;
+
+        match self.scan_manager.configure(&config) {
+            Ok(directories) => worker::start_search_directories(
+                config.clone(),
+                self.path_manager.clone(),
+                self.scan_manager.clone(),
+                &directories,
+            ),
+            Err(err) => warn!("Failed to update search directories - {}", err),
+        }
+
+        if let Err(err) = self
+            .path_manager
+            .set_patterns(NamePatterns::new(config.name_patterns()))
+        {
+            warn!("Failed to update name patterns - {}", err);
+        }
+
+        if let Err(err) = self.scheduler.configure(config.clone()) {
+            warn!("Failed to update schedules - {}", err);
+        }
+
+        healthmanager::start(config.clone(), self.health_manager.clone());
+
+        Ok(config)
+    }
+}
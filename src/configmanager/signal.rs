@@ -0,0 +1,62 @@
+use super::Reloader;
+use std::io::Error as IoError;
+use std::io::Result as IoResult;
+use std::mem;
+use std::ptr;
+use std::thread::Builder;
+
+fn reload_signal_set() -> libc::sigset_t {
+    // SAFETY: sigset_t is plain data, it is initialized by sigemptyset before use.
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGHUP);
+
+        set
+    }
+}
+
+/// Blocks SIGHUP in calling thread. Must be called before any other thread is started, so
+/// all threads inherit signal mask and signal is received only by reload thread.
+pub fn block_reload_signal() {
+    let set = reload_signal_set();
+
+    // SAFETY: set is initialized and old mask is not requested.
+    if unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, &set, ptr::null_mut()) } != 0 {
+        warn!("Failed to block SIGHUP, configuration reload on signal is not available");
+    }
+}
+
+fn wait_reload_signal() -> IoResult<()> {
+    let set = reload_signal_set();
+    let mut signal = 0;
+
+    // SAFETY: set is initialized and signal points to valid memory.
+    match unsafe { libc::sigwait(&set, &mut signal) } {
+        0 => Ok(()),
+        code => Err(IoError::from_raw_os_error(code)),
+    }
+}
+
+/// Reloads configuration on SIGHUP, invalid configuration is logged and ignored.
+pub fn start(reloader: Reloader) {
+    if let Err(err) = Builder::new()
+        .name("reload".to_string())
+        .spawn(move || loop {
+            if let Err(err) = wait_reload_signal() {
+                warn!("Failed to wait for SIGHUP - {}", err);
+
+                return;
+            }
+
+            info!("SIGHUP received, reloading configuration");
+
+            if let Err(err) = reloader.reload() {
+                warn!("Failed to reload configuration - {}", err);
+            }
+        })
+    {
+        warn!("Failed to start configuration reload thread - {}", err);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::worker::DatabaseError;
+use std::error::Error;
+use std::fmt::Arguments;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+pub type DatabaseManagerResult<T> = Result<T, DatabaseManagerError>;
+
+#[derive(Debug)]
+pub struct DatabaseManagerError {
+    message: String,
+}
+
+impl DatabaseManagerError {
+    pub fn new(message: &str) -> DatabaseManagerError {
+        DatabaseManagerError {
+            message: message.into(),
+        }
+    }
+
+    pub fn format(args: Arguments) -> DatabaseManagerError {
+        DatabaseManagerError {
+            message: format!("{}", args),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn database_error(error: DatabaseError) -> DatabaseManagerError {
+        warn!("Database error - {}", error);
+
+        DatabaseManagerError {
+            message: format!("{}", error),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for DatabaseManagerError {}
+
+impl Display for DatabaseManagerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
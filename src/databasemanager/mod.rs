@@ -0,0 +1,188 @@
+mod error;
+
+pub use self::error::DatabaseManagerError;
+pub use self::error::DatabaseManagerResult;
+
+use crate::config::Config;
+use crate::config::DestinationRef;
+use crate::configmanager::ConfigManagerRef;
+use crate::jobmanager::JobManagerRef;
+use crate::worker::DatabaseDescription;
+use crate::worker::PostgreSQL;
+use crate::worker::MAINTENANCE_DATABASE;
+
+/// Lists and drops databases on destinations, shared by HTTP handlers.
+#[derive(Debug, Clone)]
+pub struct DatabaseManager {
+    config_manager: ConfigManagerRef,
+    job_manager: JobManagerRef,
+}
+
+impl DatabaseManager {
+    pub fn new(config_manager: ConfigManagerRef, job_manager: JobManagerRef) -> DatabaseManager {
+        DatabaseManager {
+            config_manager,
+            job_manager,
+        }
+    }
+
+    pub fn databases(
+        &self,
+        destination: &DestinationRef,
+    ) -> DatabaseManagerResult<Vec<DatabaseDescription>> {
+        connect(&self.config_manager.get(), destination)?
+            .1
+            .databases()
+            .map_err(DatabaseManagerError::database_error)
+    }
+
+    /// Drops database unless it is protected or restore into it is in progress, optionally
+    /// terminating connected sessions first, setting of destination is used if not given.
+    pub fn drop_database(
+        &self,
+        destination: &DestinationRef,
+        name: &str,
+        terminate_connections: Option<bool>,
+    ) -> DatabaseManagerResult<()> {
+        let config = self.config_manager.get();
+        let (index, postgres) = connect(&config, destination)?;
+        let terminate_connections = terminate_connections
+            .unwrap_or_else(|| config.destinations()[index].terminate_connections());
+
+        if config.is_protected_database(name) {
+            return Err(DatabaseManagerError::format(format_args!(
+                "Database {} is protected",
+                name
+            )));
+        }
+
+        if self.is_restoring(&config, index, name)? {
+            return Err(DatabaseManagerError::format(format_args!(
+                "Database {} is being restored",
+                name
+            )));
+        }
+
+        let drop = || {
+            if terminate_connections {
+                let sessions = postgres
+                    .terminate_connections(name)
+                    .map_err(DatabaseManagerError::database_error)?;
+
+                for session in sessions {
+                    info!("Terminated session {} of database {}", session, name);
+                }
+            }
+
+            info!("Drop database {} on destination {}", name, destination);
+
+            postgres
+                .drop_database(name)
+                .map_err(DatabaseManagerError::database_error)
+        };
+
+        allow_connections_on_error(terminate_connections, drop, || {
+            if let Err(err) = postgres.allow_connections(name) {
+                warn!("Failed to allow connections to database {} - {}", name, err);
+            }
+        })
+    }
+
+    /// Checks if restore into database is in progress, such database must not be dropped.
+    pub fn is_restoring_database(
+        &self,
+        destination: &DestinationRef,
+        name: &str,
+    ) -> DatabaseManagerResult<bool> {
+        let config = self.config_manager.get();
+        let (index, _) = connect(&config, destination)?;
+
+        self.is_restoring(&config, index, name)
+    }
+
+    fn is_restoring(
+        &self,
+        config: &Config,
+        destination: usize,
+        name: &str,
+    ) -> DatabaseManagerResult<bool> {
+        let mut result = false;
+
+        self.job_manager
+            .for_each(|_, job| {
+                result |= !job.status().is_finished()
+                    && job.database_name() == name
+                    && job
+                        .request()
+                        .and_then(|request| config.find_destination(request.destination()))
+                        .is_some_and(|(index, _)| index == destination);
+            })
+            .map_err(|_| DatabaseManagerError::new("Job manager error"))?;
+
+        Ok(result)
+    }
+}
+
+/// Returns index of destination and connection to its maintenance database.
+fn connect(
+    config: &Config,
+    destination: &DestinationRef,
+) -> DatabaseManagerResult<(usize, PostgreSQL)> {
+    let (index, destination) = config
+        .find_destination(destination)
+        .ok_or_else(|| DatabaseManagerError::new("Invalid destination id"))?;
+
+    Ok((index, PostgreSQL::new(destination, MAINTENANCE_DATABASE)))
+}
+
+/// Calls `drop`, connections blocked by terminating sessions are allowed again if database is
+/// not dropped, so failed drop does not leave database unconnectable.
+fn allow_connections_on_error<D, A>(
+    terminate_connections: bool,
+    drop: D,
+    allow_connections: A,
+) -> DatabaseManagerResult<()>
+where
+    D: FnOnce() -> DatabaseManagerResult<()>,
+    A: FnOnce(),
+{
+    let result = drop();
+
+    if result.is_err() && terminate_connections {
+        allow_connections();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::allow_connections_on_error;
+    use super::DatabaseManagerError;
+    use std::cell::Cell;
+
+    #[test]
+    fn allow_connections_on_error_shold_allow_connections_after_failure() {
+        let allowed = Cell::new(0);
+        let allow = || allowed.set(allowed.get() + 1);
+
+        assert!(allow_connections_on_error(true, || Ok(()), allow).is_ok());
+        assert_eq!(0, allowed.get());
+
+        assert!(allow_connections_on_error(
+            true,
+            || Err(DatabaseManagerError::new("terminate failed")),
+            allow
+        )
+        .is_err());
+        assert_eq!(1, allowed.get());
+
+        assert!(allow_connections_on_error(
+            false,
+            || Err(DatabaseManagerError::new("drop failed")),
+            allow
+        )
+        .is_err());
+        assert_eq!(1, allowed.get());
+    }
+}
@@ -7,6 +7,7 @@ use std::fmt::Result as FmtResult;
 
 pub type ApplicationResult = Result<(), ApplicationError>;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum ApplicationError {
     LoadConfigError { message: String },
@@ -0,0 +1,89 @@
+use super::ExpiryEntry;
+use super::ExpiryManagerError;
+use super::ExpiryManagerResult;
+use crate::config::DestinationRef;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AuditAction {
+    Registered,
+    Unregistered,
+    Extended,
+    Expiring,
+    Dropped,
+    DropFailed,
+    Protected,
+}
+
+#[derive(Debug, Serialize)]
+struct AuditRecord<'a> {
+    time: i64,
+    action: AuditAction,
+    destination: &'a DestinationRef,
+    database_name: &'a str,
+    job_id: usize,
+    expires: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
+}
+
+/// Appends changes of expiry registry to file as JSON lines.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    pub fn new(path: Option<PathBuf>) -> AuditLog {
+        AuditLog { path }
+    }
+
+    /// Writes audit record, failures are logged but do not stop registry changes.
+    pub fn write(&self, action: AuditAction, entry: &ExpiryEntry, message: Option<&str>) {
+        info!(
+            "Database {} on destination {} - {:?}{}",
+            entry.database_name,
+            entry.destination,
+            action,
+            message.map(|m| format!(" - {}", m)).unwrap_or_default()
+        );
+
+        if let Err(err) = self.append(action, entry, message) {
+            warn!("Failed to write audit record - {}", err);
+        }
+    }
+
+    fn append(
+        &self,
+        action: AuditAction,
+        entry: &ExpiryEntry,
+        message: Option<&str>,
+    ) -> ExpiryManagerResult<()> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+        let record = AuditRecord {
+            time: OffsetDateTime::now_utc().unix_timestamp(),
+            action,
+            destination: &entry.destination,
+            database_name: &entry.database_name,
+            job_id: entry.job_id,
+            expires: entry.expires,
+            message,
+        };
+        let mut line = serde_json::to_string(&record).map_err(ExpiryManagerError::json_error)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(ExpiryManagerError::io_error)?;
+
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .map_err(ExpiryManagerError::io_error)
+    }
+}
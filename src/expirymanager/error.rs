@@ -0,0 +1,51 @@
+use serde_json::Error as JsonError;
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::io::Error as IoError;
+
+pub type ExpiryManagerResult<T> = Result<T, ExpiryManagerError>;
+
+#[derive(Debug)]
+pub struct ExpiryManagerError {
+    message: String,
+}
+
+impl ExpiryManagerError {
+    pub fn new(message: &str) -> ExpiryManagerError {
+        ExpiryManagerError {
+            message: message.into(),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn io_error(error: IoError) -> ExpiryManagerError {
+        warn!("IO error - {}", error);
+
+        ExpiryManagerError {
+            message: format!("{}", error),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn json_error(error: JsonError) -> ExpiryManagerError {
+        warn!("JSON error - {}", error);
+
+        ExpiryManagerError {
+            message: format!("{}", error),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for ExpiryManagerError {}
+
+impl Display for ExpiryManagerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
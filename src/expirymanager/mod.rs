@@ -0,0 +1,479 @@
+mod audit;
+mod error;
+mod reaper;
+
+pub use self::error::ExpiryManagerError;
+pub use self::error::ExpiryManagerResult;
+pub use self::reaper::start;
+
+use self::audit::AuditAction;
+use self::audit::AuditLog;
+use crate::config::ConfigRef;
+use crate::config::DestinationRef;
+use std::convert::TryFrom;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::RwLock;
+use time::OffsetDateTime;
+
+/// Registry of databases restored with TTL.
+#[derive(Debug, Clone)]
+pub struct ExpiryManagerRef {
+    inner: Arc<RwLock<ExpiryManager>>,
+}
+
+impl ExpiryManagerRef {
+    fn with_read<F, T>(&self, callback: F) -> ExpiryManagerResult<T>
+    where
+        F: FnOnce(&ExpiryManager) -> ExpiryManagerResult<T>,
+    {
+        match self.inner.read() {
+            Ok(ref expirymanager) => callback(expirymanager),
+            Err(err) => {
+                warn!("Failed to acquire read lock - {}", err);
+
+                Err(ExpiryManagerError::new("Failed to acquire read lock"))
+            }
+        }
+    }
+
+    fn with_write<F, T>(&self, callback: F) -> ExpiryManagerResult<T>
+    where
+        F: FnOnce(&mut ExpiryManager) -> ExpiryManagerResult<T>,
+    {
+        match self.inner.write() {
+            Ok(ref mut expirymanager) => callback(expirymanager),
+            Err(err) => {
+                warn!("Failed to acquire write lock - {}", err);
+
+                Err(ExpiryManagerError::new("Failed to acquire write lock"))
+            }
+        }
+    }
+
+    /// Checks if expiry was configured at start, registry and cleanup are not started by
+    /// configuration reload.
+    pub fn is_enabled(&self) -> bool {
+        self.with_read(|expirymanager| Ok(expirymanager.registry_path.is_some()))
+            .unwrap_or(false)
+    }
+
+    pub fn for_each<F>(&self, callback: F) -> ExpiryManagerResult<()>
+    where
+        F: FnMut(&ExpiryEntry),
+    {
+        self.with_read(move |expirymanager| {
+            expirymanager.entries.iter().for_each(callback);
+
+            Ok(())
+        })
+    }
+
+    /// Registers database restored by job, replaces expiry of already registered database.
+    pub fn register(
+        &self,
+        destination: DestinationRef,
+        database_name: &str,
+        job_id: usize,
+        ttl: u64,
+    ) -> ExpiryManagerResult<()> {
+        let now = now();
+
+        self.with_write(move |expirymanager| {
+            expirymanager.register(destination, database_name, job_id, now, ttl)
+        })
+    }
+
+    /// Removes database from registry, used when database is replaced by restore without TTL.
+    pub fn unregister(
+        &self,
+        destination: &DestinationRef,
+        database_name: &str,
+    ) -> ExpiryManagerResult<()> {
+        self.with_write(move |expirymanager| {
+            expirymanager.remove(destination, database_name, AuditAction::Unregistered, None)
+        })
+    }
+
+    /// Sets expiry of registered database to `ttl` seconds from now, returns new expiry time.
+    pub fn extend(
+        &self,
+        destination: &DestinationRef,
+        database_name: &str,
+        ttl: u64,
+    ) -> ExpiryManagerResult<i64> {
+        let now = now();
+
+        self.with_write(move |expirymanager| {
+            expirymanager.extend(destination, database_name, now, ttl)
+        })
+    }
+
+    fn take_expiring(&self, now: i64) -> ExpiryManagerResult<Vec<ExpiryEntry>> {
+        self.with_write(move |expirymanager| expirymanager.take_expiring(now))
+    }
+
+    fn expired(&self, now: i64) -> ExpiryManagerResult<Vec<ExpiryEntry>> {
+        self.with_read(move |expirymanager| {
+            Ok(expirymanager
+                .entries
+                .iter()
+                .filter(|entry| entry.expires <= now)
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn remove(
+        &self,
+        entry: &ExpiryEntry,
+        action: AuditAction,
+        message: Option<&str>,
+    ) -> ExpiryManagerResult<()> {
+        self.with_write(move |expirymanager| {
+            expirymanager.remove(&entry.destination, &entry.database_name, action, message)
+        })
+    }
+
+    /// Audits failed drop of expired database, returns `false` if failure is already audited.
+    fn drop_failed(&self, entry: &ExpiryEntry, message: &str) -> ExpiryManagerResult<bool> {
+        self.with_write(move |expirymanager| {
+            expirymanager.drop_failed(&entry.destination, &entry.database_name, message)
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ExpiryManager {
+    registry_path: Option<PathBuf>,
+    warning: i64,
+    audit: AuditLog,
+    entries: Vec<ExpiryEntry>,
+}
+
+impl ExpiryManager {
+    fn new(registry_path: Option<PathBuf>, warning: u64, audit: AuditLog) -> ExpiryManager {
+        ExpiryManager {
+            registry_path,
+            warning: seconds(warning),
+            audit,
+            entries: Vec::new(),
+        }
+    }
+
+    fn register(
+        &mut self,
+        destination: DestinationRef,
+        database_name: &str,
+        job_id: usize,
+        now: i64,
+        ttl: u64,
+    ) -> ExpiryManagerResult<()> {
+        let entry = ExpiryEntry {
+            destination,
+            database_name: database_name.into(),
+            job_id,
+            created: now,
+            expires: now.saturating_add(seconds(ttl)),
+            warned: false,
+            drop_failed: false,
+        };
+
+        self.audit.write(AuditAction::Registered, &entry, None);
+
+        match self.position(&entry.destination, database_name) {
+            Some(index) => self.entries[index] = entry,
+            None => self.entries.push(entry),
+        }
+
+        self.save()
+    }
+
+    fn extend(
+        &mut self,
+        destination: &DestinationRef,
+        database_name: &str,
+        now: i64,
+        ttl: u64,
+    ) -> ExpiryManagerResult<i64> {
+        let index = self
+            .position(destination, database_name)
+            .ok_or_else(|| ExpiryManagerError::new("Database is not registered"))?;
+        let entry = &mut self.entries[index];
+
+        entry.expires = now.saturating_add(seconds(ttl));
+        entry.warned = false;
+        entry.drop_failed = false;
+
+        let expires = entry.expires;
+
+        self.audit
+            .write(AuditAction::Extended, &self.entries[index], None);
+        self.save()?;
+
+        Ok(expires)
+    }
+
+    fn remove(
+        &mut self,
+        destination: &DestinationRef,
+        database_name: &str,
+        action: AuditAction,
+        message: Option<&str>,
+    ) -> ExpiryManagerResult<()> {
+        if let Some(index) = self.position(destination, database_name) {
+            let entry = self.entries.remove(index);
+
+            self.audit.write(action, &entry, message);
+            self.save()?;
+        }
+
+        Ok(())
+    }
+
+    fn drop_failed(
+        &mut self,
+        destination: &DestinationRef,
+        database_name: &str,
+        message: &str,
+    ) -> ExpiryManagerResult<bool> {
+        let index = match self.position(destination, database_name) {
+            Some(index) if !self.entries[index].drop_failed => index,
+            _ => return Ok(false),
+        };
+
+        self.entries[index].drop_failed = true;
+        self.audit
+            .write(AuditAction::DropFailed, &self.entries[index], Some(message));
+        self.save()?;
+
+        Ok(true)
+    }
+
+    /// Marks entries which expire within warning period and returns entries not warned before.
+    fn take_expiring(&mut self, now: i64) -> ExpiryManagerResult<Vec<ExpiryEntry>> {
+        let mut result = Vec::new();
+
+        for entry in &mut self.entries {
+            if !entry.warned && entry.expires > now && entry.expires - now <= self.warning {
+                entry.warned = true;
+                result.push(entry.clone());
+            }
+        }
+
+        if !result.is_empty() {
+            for entry in &result {
+                self.audit.write(AuditAction::Expiring, entry, None);
+            }
+
+            self.save()?;
+        }
+
+        Ok(result)
+    }
+
+    fn position(&self, destination: &DestinationRef, database_name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| {
+            entry.destination == *destination && entry.database_name == database_name
+        })
+    }
+
+    fn save(&self) -> ExpiryManagerResult<()> {
+        let registry_path = match self.registry_path {
+            Some(ref registry_path) => registry_path,
+            None => return Ok(()),
+        };
+        let temp_path = registry_path.with_extension("tmp");
+        let writer = File::create(&temp_path).map_err(ExpiryManagerError::io_error)?;
+
+        serde_json::to_writer_pretty(BufWriter::new(writer), &self.entries)
+            .map_err(ExpiryManagerError::json_error)?;
+        fs::rename(&temp_path, registry_path).map_err(ExpiryManagerError::io_error)?;
+
+        Ok(())
+    }
+
+    fn load(&mut self) -> ExpiryManagerResult<()> {
+        let registry_path = match self.registry_path {
+            Some(ref registry_path) if registry_path.is_file() => registry_path,
+            _ => return Ok(()),
+        };
+        let reader = File::open(registry_path).map_err(ExpiryManagerError::io_error)?;
+
+        self.entries = serde_json::from_reader(BufReader::new(reader))
+            .map_err(ExpiryManagerError::json_error)?;
+
+        info!(
+            "Loaded {} expiring databases from {}",
+            self.entries.len(),
+            registry_path.display()
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpiryEntry {
+    destination: DestinationRef,
+    database_name: String,
+    job_id: usize,
+    created: i64,
+    expires: i64,
+    #[serde(default)]
+    warned: bool,
+    /// Set after first failed drop, so repeated failures are audited once.
+    #[serde(default)]
+    drop_failed: bool,
+}
+
+impl ExpiryEntry {
+    pub fn destination(&self) -> &DestinationRef {
+        &self.destination
+    }
+
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    pub fn job_id(&self) -> usize {
+        self.job_id
+    }
+
+    pub fn created(&self) -> i64 {
+        self.created
+    }
+
+    pub fn expires(&self) -> i64 {
+        self.expires
+    }
+
+    /// Checks if expiry warning is issued, reset when expiry is extended.
+    pub fn warned(&self) -> bool {
+        self.warned
+    }
+}
+
+fn now() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+fn seconds(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn create(config: ConfigRef) -> ExpiryManagerRef {
+    let expiry_manager = match config.expiry() {
+        Some(expiry) => {
+            let mut expiry_manager = ExpiryManager::new(
+                Some(PathBuf::from(expiry.registry_path())),
+                expiry.warning(),
+                AuditLog::new(expiry.audit_path().map(PathBuf::from)),
+            );
+
+            if let Err(err) = expiry_manager.load() {
+                warn!("Failed to load expiry registry - {}", err);
+            }
+
+            expiry_manager
+        }
+        None => ExpiryManager::new(None, 0, AuditLog::new(None)),
+    };
+
+    ExpiryManagerRef {
+        inner: Arc::new(RwLock::new(expiry_manager)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditLog;
+    use super::ExpiryManager;
+    use crate::config::DestinationRef;
+
+    fn manager() -> ExpiryManager {
+        ExpiryManager::new(None, 100, AuditLog::new(None))
+    }
+
+    #[test]
+    fn register_shold_replace_existing_entry() {
+        let mut manager = manager();
+
+        manager
+            .register(DestinationRef::Index(0), "sales", 1, 1000, 60)
+            .unwrap();
+        manager
+            .register(DestinationRef::Index(1), "sales", 2, 1000, 60)
+            .unwrap();
+        manager
+            .register(DestinationRef::Index(0), "sales", 3, 2000, 60)
+            .unwrap();
+
+        assert_eq!(2, manager.entries.len());
+        assert_eq!(3, manager.entries[0].job_id);
+        assert_eq!(2060, manager.entries[0].expires);
+    }
+
+    #[test]
+    fn take_expiring_shold_warn_once() {
+        let mut manager = manager();
+
+        manager
+            .register(DestinationRef::Index(0), "sales", 1, 1000, 500)
+            .unwrap();
+        manager
+            .register(DestinationRef::Index(0), "stock", 2, 1000, 50)
+            .unwrap();
+
+        let expiring = manager.take_expiring(1000).unwrap();
+
+        assert_eq!(1, expiring.len());
+        assert_eq!("stock", expiring[0].database_name);
+        assert!(manager.take_expiring(1000).unwrap().is_empty());
+        assert_eq!(1, manager.take_expiring(1450).unwrap().len());
+    }
+
+    #[test]
+    fn extend_shold_reset_warning() {
+        let mut manager = manager();
+
+        manager
+            .register(DestinationRef::Index(0), "sales", 1, 1000, 50)
+            .unwrap();
+        manager.take_expiring(1000).unwrap();
+
+        assert_eq!(
+            1500,
+            manager
+                .extend(&DestinationRef::Index(0), "sales", 1000, 500)
+                .unwrap()
+        );
+        assert!(!manager.entries[0].warned);
+        assert!(manager
+            .extend(&DestinationRef::Index(0), "stock", 1000, 500)
+            .is_err());
+    }
+
+    #[test]
+    fn drop_failed_shold_audit_once_until_extended() {
+        let mut manager = manager();
+        let destination = DestinationRef::Index(0);
+
+        manager
+            .register(destination.clone(), "sales", 1, 1000, 50)
+            .unwrap();
+
+        assert!(manager.drop_failed(&destination, "sales", "error").unwrap());
+        assert!(!manager.drop_failed(&destination, "sales", "error").unwrap());
+        assert!(!manager.drop_failed(&destination, "stock", "error").unwrap());
+
+        manager.extend(&destination, "sales", 1000, 50).unwrap();
+
+        assert!(manager.drop_failed(&destination, "sales", "error").unwrap());
+    }
+}
@@ -0,0 +1,136 @@
+use super::audit::AuditAction;
+use super::now;
+use super::ExpiryEntry;
+use super::ExpiryManagerError;
+use super::ExpiryManagerRef;
+use super::ExpiryManagerResult;
+use crate::config::ConfigRef;
+use crate::configmanager::ConfigManagerRef;
+use crate::databasemanager::DatabaseManager;
+use std::thread;
+use std::thread::Builder;
+use std::time::Duration;
+
+fn run(
+    config_manager: &ConfigManagerRef,
+    expiry_manager: &ExpiryManagerRef,
+    database_manager: &DatabaseManager,
+    interval: Duration,
+) {
+    loop {
+        let now = now();
+
+        match expiry_manager.take_expiring(now) {
+            Ok(entries) => {
+                for entry in entries {
+                    warn!(
+                        "Database {} on destination {} expires at {}",
+                        entry.database_name, entry.destination, entry.expires
+                    );
+                }
+            }
+            Err(err) => warn!("Failed to check expiring databases - {}", err),
+        }
+
+        let config = config_manager.get();
+
+        match expiry_manager.expired(now) {
+            Ok(entries) => {
+                for entry in entries {
+                    if let Err(err) =
+                        drop_expired(&config, expiry_manager, database_manager, &entry)
+                    {
+                        warn!(
+                            "Failed to clean up expired database {} - {}",
+                            entry.database_name, err
+                        );
+                    }
+                }
+            }
+            Err(err) => warn!("Failed to check expired databases - {}", err),
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+/// Drops expired database, keeps entry for next check if database can not be dropped or is
+/// being restored. Only first failure is audited.
+fn drop_expired(
+    config: &ConfigRef,
+    expiry_manager: &ExpiryManagerRef,
+    database_manager: &DatabaseManager,
+    entry: &ExpiryEntry,
+) -> ExpiryManagerResult<()> {
+    let destination = match config.find_destination(&entry.destination) {
+        Some((_, destination)) => destination,
+        None => {
+            return expiry_manager.remove(
+                entry,
+                AuditAction::DropFailed,
+                Some("Destination does not exist"),
+            )
+        }
+    };
+
+    if config.is_protected_database(&entry.database_name) {
+        return expiry_manager.remove(entry, AuditAction::Protected, None);
+    }
+
+    let restoring = database_manager
+        .is_restoring_database(&entry.destination, &entry.database_name)
+        .map_err(|err| ExpiryManagerError::new(err.message()))?;
+
+    if restoring {
+        debug!(
+            "Expired database {} is being restored, drop postponed",
+            entry.database_name
+        );
+
+        return Ok(());
+    }
+
+    match database_manager.drop_database(
+        &entry.destination,
+        &entry.database_name,
+        Some(destination.terminate_connections()),
+    ) {
+        Ok(()) => expiry_manager.remove(entry, AuditAction::Dropped, None),
+        Err(err) => {
+            if expiry_manager.drop_failed(entry, err.message())? {
+                warn!(
+                    "Failed to drop expired database {} - {}",
+                    entry.database_name,
+                    err.message()
+                );
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Periodically drops expired databases, failed checks are logged and retried on next check.
+/// Check interval is read once, destinations and protected databases are read from current
+/// configuration on every check.
+pub fn start(
+    config_manager: ConfigManagerRef,
+    expiry_manager: ExpiryManagerRef,
+    database_manager: DatabaseManager,
+) {
+    let interval = match config_manager.get().expiry() {
+        Some(expiry) => Duration::from_secs(expiry.interval()),
+        None => return,
+    };
+
+    if let Err(err) = Builder::new().name("reaper".to_string()).spawn(move || {
+        run(
+            &config_manager,
+            &expiry_manager,
+            &database_manager,
+            interval,
+        )
+    }) {
+        warn!("Failed to start expired databases cleanup - {}", err);
+    }
+}
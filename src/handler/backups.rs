@@ -0,0 +1,51 @@
+use super::search::SearchResult;
+use super::util::handle_empty;
+use super::HandlerError;
+use crate::pathmanager::PathManagerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct BackupsHandler {
+    path_manager: PathManagerRef,
+}
+
+impl BackupsHandler {
+    pub fn new(path_manager: PathManagerRef) -> BackupsHandler {
+        BackupsHandler { path_manager }
+    }
+}
+
+impl Handler for BackupsHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let mut result: Vec<DatabaseBackups> = Vec::new();
+
+            self.path_manager
+                .for_each_database(|database, backups| {
+                    let backups: Vec<_> = backups
+                        .iter()
+                        .map(|entry| SearchResult::new(entry))
+                        .collect();
+
+                    result.push(DatabaseBackups {
+                        database: database.into(),
+                        latest: backups.first().map(|backup| backup.path().into()),
+                        backups,
+                    })
+                })
+                .map_err(|_| HandlerError::new("Failed to query backups"))?;
+
+            Ok(result)
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DatabaseBackups {
+    database: String,
+    latest: Option<String>,
+    backups: Vec<SearchResult>,
+}
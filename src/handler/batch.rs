@@ -0,0 +1,29 @@
+use super::util::handle_request;
+use super::HandlerError;
+use crate::launcher::BatchRequest;
+use crate::launcher::Launcher;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct BatchRestoreHandler {
+    launcher: Launcher,
+}
+
+impl BatchRestoreHandler {
+    pub fn new(launcher: Launcher) -> BatchRestoreHandler {
+        BatchRestoreHandler { launcher }
+    }
+}
+
+impl Handler for BatchRestoreHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: BatchRequest| {
+            self.launcher
+                .launch_batch(request)
+                .map_err(|err| HandlerError::new(err.message()))
+        })
+    }
+}
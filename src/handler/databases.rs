@@ -0,0 +1,116 @@
+use super::util::handle_request;
+use super::HandlerError;
+use crate::config::DestinationRef;
+use crate::configmanager::ConfigManagerRef;
+use crate::databasemanager::DatabaseManager;
+use crate::expirymanager::ExpiryManagerRef;
+use crate::worker::DatabaseDescription;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct DatabasesHandler {
+    database_manager: DatabaseManager,
+}
+
+impl DatabasesHandler {
+    pub fn new(database_manager: DatabaseManager) -> DatabasesHandler {
+        DatabasesHandler { database_manager }
+    }
+}
+
+impl Handler for DatabasesHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: DestinationParams| {
+            let databases = self
+                .database_manager
+                .databases(&request.destination)
+                .map_err(|err| HandlerError::new(err.message()))?;
+
+            Ok(databases.iter().map(DatabaseData::new).collect::<Vec<_>>())
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct DropDatabaseHandler {
+    config_manager: ConfigManagerRef,
+    database_manager: DatabaseManager,
+    expiry_manager: ExpiryManagerRef,
+}
+
+impl DropDatabaseHandler {
+    pub fn new(
+        config_manager: ConfigManagerRef,
+        database_manager: DatabaseManager,
+        expiry_manager: ExpiryManagerRef,
+    ) -> DropDatabaseHandler {
+        DropDatabaseHandler {
+            config_manager,
+            database_manager,
+            expiry_manager,
+        }
+    }
+}
+
+impl Handler for DropDatabaseHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: DatabaseParams| {
+            self.database_manager
+                .drop_database(
+                    &request.destination,
+                    &request.name,
+                    request.terminate_connections,
+                )
+                .map_err(|err| HandlerError::new(err.message()))?;
+
+            match self
+                .config_manager
+                .get()
+                .canonical_destination(&request.destination)
+            {
+                Some(destination) => self
+                    .expiry_manager
+                    .unregister(&destination, &request.name)
+                    .map_err(|err| HandlerError::new(err.message())),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DestinationParams {
+    destination: DestinationRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseParams {
+    destination: DestinationRef,
+    name: String,
+    #[serde(default)]
+    terminate_connections: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct DatabaseData {
+    name: String,
+    owner: String,
+    size: Option<i64>,
+    created: Option<i64>,
+    connections: i64,
+}
+
+impl DatabaseData {
+    fn new(database: &DatabaseDescription) -> DatabaseData {
+        DatabaseData {
+            name: database.name().into(),
+            owner: database.owner().into(),
+            size: database.size(),
+            created: database.created(),
+            connections: database.connections(),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use super::util::handle_empty;
+use super::HandlerError;
+use crate::configmanager::ConfigManagerRef;
+use crate::healthmanager::DestinationHealth;
+use crate::healthmanager::HealthManagerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct CheckDestinationsHandler {
+    config_manager: ConfigManagerRef,
+    health_manager: HealthManagerRef,
+}
+
+impl CheckDestinationsHandler {
+    pub fn new(
+        config_manager: ConfigManagerRef,
+        health_manager: HealthManagerRef,
+    ) -> CheckDestinationsHandler {
+        CheckDestinationsHandler {
+            config_manager,
+            health_manager,
+        }
+    }
+}
+
+impl Handler for CheckDestinationsHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let config = self.config_manager.get();
+            let result = self
+                .health_manager
+                .check(&config)
+                .map_err(|_| HandlerError::new("Health manager error"))?;
+
+            Ok(config
+                .destinations()
+                .iter()
+                .zip(result)
+                .enumerate()
+                .map(|(index, (destination, health))| DestinationCheck {
+                    index,
+                    id: destination.id().cloned(),
+                    name: destination.display_name(),
+                    health,
+                })
+                .collect::<Vec<_>>())
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DestinationCheck {
+    index: usize,
+    id: Option<String>,
+    name: String,
+    #[serde(flatten)]
+    health: DestinationHealth,
+}
@@ -0,0 +1,104 @@
+use super::util::handle_empty;
+use super::util::handle_request;
+use super::HandlerError;
+use crate::config::DestinationRef;
+use crate::configmanager::ConfigManagerRef;
+use crate::expirymanager::ExpiryEntry;
+use crate::expirymanager::ExpiryManagerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct ExpiryHandler {
+    expiry_manager: ExpiryManagerRef,
+}
+
+impl ExpiryHandler {
+    pub fn new(expiry_manager: ExpiryManagerRef) -> ExpiryHandler {
+        ExpiryHandler { expiry_manager }
+    }
+}
+
+impl Handler for ExpiryHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let mut result = Vec::new();
+
+            self.expiry_manager
+                .for_each(|entry| result.push(ExpiryData::new(entry)))
+                .map_err(|_| HandlerError::new("Expiry manager error"))?;
+
+            Ok(result)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtendExpiryHandler {
+    config_manager: ConfigManagerRef,
+    expiry_manager: ExpiryManagerRef,
+}
+
+impl ExtendExpiryHandler {
+    pub fn new(
+        config_manager: ConfigManagerRef,
+        expiry_manager: ExpiryManagerRef,
+    ) -> ExtendExpiryHandler {
+        ExtendExpiryHandler {
+            config_manager,
+            expiry_manager,
+        }
+    }
+}
+
+impl Handler for ExtendExpiryHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: ExtendRequest| {
+            if request.ttl == 0 {
+                return Err(HandlerError::new("TTL must be positive"));
+            }
+
+            let destination = self
+                .config_manager
+                .get()
+                .canonical_destination(&request.destination)
+                .ok_or_else(|| HandlerError::new("Invalid destination id"))?;
+
+            self.expiry_manager
+                .extend(&destination, &request.database_name, request.ttl)
+                .map_err(|err| HandlerError::new(err.message()))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtendRequest {
+    destination: DestinationRef,
+    database_name: String,
+    ttl: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ExpiryData {
+    destination: DestinationRef,
+    database_name: String,
+    jobid: usize,
+    created: i64,
+    expires: i64,
+    expiring: bool,
+}
+
+impl ExpiryData {
+    fn new(entry: &ExpiryEntry) -> ExpiryData {
+        ExpiryData {
+            destination: entry.destination().clone(),
+            database_name: entry.database_name().into(),
+            jobid: entry.job_id(),
+            created: entry.created(),
+            expires: entry.expires(),
+            expiring: entry.warned(),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::util::handle_empty;
 use super::HandlerError;
+use crate::jobmanager::Job;
 use crate::jobmanager::JobManagerRef;
 use crate::jobmanager::JobStatus;
 use iron::middleware::Handler;
@@ -24,16 +25,7 @@ impl Handler for JobsHandler {
             let mut result: Vec<JobData> = Vec::new();
 
             self.job_manager
-                .for_each(|jobid, job| {
-                    result.push(JobData::new(
-                        jobid,
-                        job.created(),
-                        job.modified(),
-                        job.database_name(),
-                        job.status(),
-                        job.stage(),
-                    ))
-                })
+                .for_each(|jobid, job| result.push(JobData::new(jobid, job)))
                 .map_err(|_| HandlerError::new("Job manager error"))?;
 
             Ok(result)
@@ -49,18 +41,13 @@ struct JobData {
     database_name: String,
     status: String,
     stage: Option<String>,
+    parent: Option<usize>,
+    children: Vec<usize>,
 }
 
 impl JobData {
-    fn new(
-        jobid: usize,
-        created: i64,
-        modified: i64,
-        database_name: &str,
-        status: &JobStatus,
-        stage: Option<&String>,
-    ) -> JobData {
-        let status = match status {
+    fn new(jobid: usize, job: &Job) -> JobData {
+        let status = match job.status() {
             JobStatus::Pending => "Pending",
             JobStatus::Aborted => "Aborted",
             JobStatus::InProgress => "InProgress",
@@ -71,11 +58,13 @@ impl JobData {
 
         JobData {
             jobid,
-            created,
-            modified,
-            database_name: database_name.into(),
+            created: job.created(),
+            modified: job.modified(),
+            database_name: job.database_name().into(),
             status: status.into(),
-            stage: stage.cloned(),
+            stage: job.stage().cloned(),
+            parent: job.parent(),
+            children: job.children().into(),
         }
     }
 }
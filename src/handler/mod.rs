@@ -1,17 +1,47 @@
 mod abort;
 mod settings;
+mod backups;
+mod batch;
+mod databases;
+mod destinations;
 mod error;
+mod expiry;
 mod jobs;
+mod presets;
+mod reload;
+mod rerun;
+mod rescan;
 mod restore;
+mod scanstatus;
+mod schedules;
 mod search;
 mod status;
+mod suggest;
 mod util;
 
 pub use self::abort::AbortHandler;
 pub use self::settings::SettingsHandler;
+pub use self::backups::BackupsHandler;
+pub use self::batch::BatchRestoreHandler;
+pub use self::databases::DatabasesHandler;
+pub use self::databases::DropDatabaseHandler;
+pub use self::destinations::CheckDestinationsHandler;
 pub use self::error::HandlerError;
 pub use self::error::HandlerResult;
+pub use self::expiry::ExpiryHandler;
+pub use self::expiry::ExtendExpiryHandler;
 pub use self::jobs::JobsHandler;
+pub use self::presets::CreatePresetHandler;
+pub use self::presets::DeletePresetHandler;
+pub use self::presets::LaunchPresetHandler;
+pub use self::presets::PresetsHandler;
+pub use self::presets::UpdatePresetHandler;
+pub use self::reload::ReloadConfigHandler;
+pub use self::rerun::RerunHandler;
+pub use self::rescan::RescanHandler;
 pub use self::restore::RestoreHandler;
+pub use self::scanstatus::ScanStatusHandler;
+pub use self::schedules::SchedulesHandler;
 pub use self::search::SearchHandler;
 pub use self::status::StatusHandler;
+pub use self::suggest::SuggestHandler;
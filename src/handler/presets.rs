@@ -0,0 +1,174 @@
+use super::util::handle_empty;
+use super::util::handle_request;
+use super::HandlerError;
+use crate::launcher::Backup;
+use crate::launcher::Launcher;
+use crate::launcher::RestoreRequest;
+use crate::presetmanager::Preset;
+use crate::presetmanager::PresetData;
+use crate::presetmanager::PresetManagerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct PresetsHandler {
+    preset_manager: PresetManagerRef,
+}
+
+impl PresetsHandler {
+    pub fn new(preset_manager: PresetManagerRef) -> PresetsHandler {
+        PresetsHandler { preset_manager }
+    }
+}
+
+impl Handler for PresetsHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let mut result: Vec<Preset> = Vec::new();
+
+            self.preset_manager
+                .for_each(|preset| result.push(preset.clone()))
+                .map_err(|_| HandlerError::new("Preset manager error"))?;
+
+            Ok(result)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CreatePresetHandler {
+    preset_manager: PresetManagerRef,
+    launcher: Launcher,
+}
+
+impl CreatePresetHandler {
+    pub fn new(preset_manager: PresetManagerRef, launcher: Launcher) -> CreatePresetHandler {
+        CreatePresetHandler {
+            preset_manager,
+            launcher,
+        }
+    }
+}
+
+impl Handler for CreatePresetHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: PresetData| {
+            let restore_request = self
+                .launcher
+                .validate(request.request().clone())
+                .map_err(|err| HandlerError::new(err.message()))?;
+
+            self.preset_manager
+                .create(request.with_request(restore_request))
+                .map_err(|err| HandlerError::new(&format!("{}", err)))
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdatePresetHandler {
+    preset_manager: PresetManagerRef,
+    launcher: Launcher,
+}
+
+impl UpdatePresetHandler {
+    pub fn new(preset_manager: PresetManagerRef, launcher: Launcher) -> UpdatePresetHandler {
+        UpdatePresetHandler {
+            preset_manager,
+            launcher,
+        }
+    }
+}
+
+impl Handler for UpdatePresetHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: UpdateRequest| {
+            let restore_request = self
+                .launcher
+                .validate(request.preset.request().clone())
+                .map_err(|err| HandlerError::new(err.message()))?;
+
+            self.preset_manager
+                .update(request.id, request.preset.with_request(restore_request))
+                .map_err(|err| HandlerError::new(&format!("{}", err)))
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct DeletePresetHandler {
+    preset_manager: PresetManagerRef,
+}
+
+impl DeletePresetHandler {
+    pub fn new(preset_manager: PresetManagerRef) -> DeletePresetHandler {
+        DeletePresetHandler { preset_manager }
+    }
+}
+
+impl Handler for DeletePresetHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: IdRequest| {
+            self.preset_manager
+                .delete(request.id)
+                .map_err(|err| HandlerError::new(&format!("{}", err)))
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct LaunchPresetHandler {
+    preset_manager: PresetManagerRef,
+    launcher: Launcher,
+}
+
+impl LaunchPresetHandler {
+    pub fn new(preset_manager: PresetManagerRef, launcher: Launcher) -> LaunchPresetHandler {
+        LaunchPresetHandler {
+            preset_manager,
+            launcher,
+        }
+    }
+}
+
+impl Handler for LaunchPresetHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: LaunchRequest| {
+            let preset = self
+                .preset_manager
+                .get(request.id)
+                .map_err(|err| HandlerError::new(&format!("{}", err)))?;
+            let restore_request: RestoreRequest = preset
+                .request()
+                .clone()
+                .with_overrides(request.backup, request.database_name);
+
+            self.launcher
+                .launch(restore_request)
+                .map_err(|err| HandlerError::new(err.message()))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateRequest {
+    id: usize,
+    #[serde(flatten)]
+    preset: PresetData,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdRequest {
+    id: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct LaunchRequest {
+    id: usize,
+    #[serde(default)]
+    backup: Option<Backup>,
+    #[serde(default)]
+    database_name: Option<String>,
+}
@@ -0,0 +1,42 @@
+use super::util::handle_empty;
+use super::HandlerError;
+use crate::configmanager::Reloader;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct ReloadConfigHandler {
+    reloader: Reloader,
+}
+
+impl ReloadConfigHandler {
+    pub fn new(reloader: Reloader) -> ReloadConfigHandler {
+        ReloadConfigHandler { reloader }
+    }
+}
+
+impl Handler for ReloadConfigHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let config = self
+                .reloader
+                .reload()
+                .map_err(|err| HandlerError::new(err.message()))?;
+
+            Ok(ReloadData {
+                destinations: config.destinations().len(),
+                directories: config.search_config().directories().len(),
+                schedules: config.schedules().len(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ReloadData {
+    destinations: usize,
+    directories: usize,
+    schedules: usize,
+}
@@ -0,0 +1,33 @@
+use super::util::handle_request;
+use super::HandlerError;
+use crate::launcher::Launcher;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct RerunHandler {
+    launcher: Launcher,
+}
+
+impl RerunHandler {
+    pub fn new(launcher: Launcher) -> RerunHandler {
+        RerunHandler { launcher }
+    }
+}
+
+impl Handler for RerunHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: Request| {
+            self.launcher
+                .rerun(request.jobid)
+                .map_err(|err| HandlerError::new(err.message()))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    jobid: usize,
+}
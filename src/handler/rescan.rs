@@ -0,0 +1,35 @@
+use super::util::handle_request;
+use super::HandlerError;
+use crate::scanmanager::ScanManagerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct RescanHandler {
+    scan_manager: ScanManagerRef,
+}
+
+impl RescanHandler {
+    pub fn new(scan_manager: ScanManagerRef) -> RescanHandler {
+        RescanHandler { scan_manager }
+    }
+}
+
+impl Handler for RescanHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: Request| {
+            self.scan_manager
+                .request_rescan(request.directory.as_ref().map(Path::new))
+                .map_err(|err| HandlerError::new(&format!("Failed to request rescan - {}", err)))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    directory: Option<String>,
+}
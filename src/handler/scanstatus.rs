@@ -0,0 +1,61 @@
+use super::util::handle_empty;
+use super::HandlerError;
+use crate::scanmanager::ScanManagerRef;
+use crate::scanmanager::ScanStatus;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct ScanStatusHandler {
+    scan_manager: ScanManagerRef,
+}
+
+impl ScanStatusHandler {
+    pub fn new(scan_manager: ScanManagerRef) -> ScanStatusHandler {
+        ScanStatusHandler { scan_manager }
+    }
+}
+
+impl Handler for ScanStatusHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let mut result: Vec<ScanStatusData> = Vec::new();
+
+            self.scan_manager
+                .for_each(|directory, status| result.push(ScanStatusData::new(directory, status)))
+                .map_err(|_| HandlerError::new("Scan manager error"))?;
+
+            Ok(result)
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ScanStatusData {
+    directory: String,
+    in_progress: bool,
+    last_scan_start: Option<i64>,
+    last_scan_end: Option<i64>,
+    duration_ms: Option<u64>,
+    files_found: usize,
+    errors_count: usize,
+    errors: Vec<String>,
+}
+
+impl ScanStatusData {
+    fn new(directory: &Path, status: &ScanStatus) -> ScanStatusData {
+        ScanStatusData {
+            directory: directory.display().to_string(),
+            in_progress: status.in_progress(),
+            last_scan_start: status.started(),
+            last_scan_end: status.finished(),
+            duration_ms: status.duration(),
+            files_found: status.files_found(),
+            errors_count: status.errors_count(),
+            errors: status.errors().to_vec(),
+        }
+    }
+}
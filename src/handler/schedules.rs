@@ -0,0 +1,61 @@
+use super::util::handle_empty;
+use super::HandlerError;
+use crate::launcher::RestoreRequest;
+use crate::scheduler::Schedule;
+use crate::scheduler::SchedulerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct SchedulesHandler {
+    scheduler: SchedulerRef,
+}
+
+impl SchedulesHandler {
+    pub fn new(scheduler: SchedulerRef) -> SchedulesHandler {
+        SchedulesHandler { scheduler }
+    }
+}
+
+impl Handler for SchedulesHandler {
+    fn handle(&self, _request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_empty(move || {
+            let mut result: Vec<ScheduleData> = Vec::new();
+
+            self.scheduler
+                .for_each(|schedule| result.push(ScheduleData::new(schedule)))
+                .map_err(|_| HandlerError::new("Scheduler error"))?;
+
+            Ok(result)
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ScheduleData {
+    name: String,
+    cron: String,
+    enabled: bool,
+    request: RestoreRequest,
+    next_run: Option<i64>,
+    last_run: Option<i64>,
+    last_job_id: Option<usize>,
+    last_error: Option<String>,
+}
+
+impl ScheduleData {
+    fn new(schedule: &Schedule) -> ScheduleData {
+        ScheduleData {
+            name: schedule.name().into(),
+            cron: schedule.expression().into(),
+            enabled: schedule.enabled(),
+            request: schedule.request().clone(),
+            next_run: schedule.next_run(),
+            last_run: schedule.last_run(),
+            last_job_id: schedule.last_job_id(),
+            last_error: schedule.last_error().cloned(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use super::util::handle_request;
 use super::HandlerError;
+use crate::pathmanager::BackupEntry;
+use crate::pathmanager::BackupFormat;
 use crate::pathmanager::PathManagerRef;
+use crate::pathmanager::SearchOrder;
 use iron::middleware::Handler;
 use iron::IronResult;
 use iron::Request as IronRequest;
@@ -20,18 +23,76 @@ impl SearchHandler {
 impl Handler for SearchHandler {
     fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
         handle_request(request, move |request: Request| {
-            let mut result: Vec<String> = Vec::new();
-
-            self.path_manager
-                .query_paths(&request.query, 20, |p| result.push(p.display().to_string()))
+            let mut results: Vec<SearchResult> = Vec::new();
+            let paged = request.offset.is_some() || request.limit.is_some();
+            let limit = request.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+            let total = self
+                .path_manager
+                .query_paths(
+                    &request.query,
+                    request.order,
+                    request.offset.unwrap_or_default(),
+                    limit,
+                    |entry| results.push(SearchResult::new(entry)),
+                )
                 .map_err(|_| HandlerError::new("Failed to query results"))?;
 
-            Ok(result)
+            if paged {
+                Ok(Response::Page { total, results })
+            } else {
+                Ok(Response::Results(results))
+            }
         })
     }
 }
 
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
 #[derive(Debug, Deserialize)]
 struct Request {
     query: String,
+    #[serde(default)]
+    order: SearchOrder,
+    #[serde(default)]
+    offset: Option<usize>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// Plain list of results is returned unless `offset` or `limit` is requested, so clients of
+/// unpaged search keep working.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Response {
+    Results(Vec<SearchResult>),
+    Page {
+        total: usize,
+        results: Vec<SearchResult>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    path: String,
+    size: u64,
+    modified: i64,
+    format: BackupFormat,
+    dump_timestamp: Option<i64>,
+}
+
+impl SearchResult {
+    pub fn new(entry: &BackupEntry) -> SearchResult {
+        SearchResult {
+            path: entry.path().display().to_string(),
+            size: entry.size(),
+            modified: entry.modified(),
+            format: entry.format(),
+            dump_timestamp: entry.dump_timestamp(),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
 }
@@ -1,5 +1,9 @@
 use super::util::handle_empty;
-use crate::config::ConfigRef;
+use super::HandlerError;
+use crate::config::Destination as ConfigDestination;
+use crate::configmanager::ConfigManagerRef;
+use crate::healthmanager::DestinationHealth;
+use crate::healthmanager::HealthManagerRef;
 use iron::middleware::Handler;
 use iron::IronResult;
 use iron::Request as IromRequest;
@@ -7,29 +11,35 @@ use iron::Response as IromResponse;
 
 #[derive(Debug)]
 pub struct SettingsHandler {
-    config: ConfigRef,
+    config_manager: ConfigManagerRef,
+    health_manager: HealthManagerRef,
 }
 
 impl SettingsHandler {
-    pub fn new(config: ConfigRef) -> SettingsHandler {
-        SettingsHandler { config }
+    pub fn new(
+        config_manager: ConfigManagerRef,
+        health_manager: HealthManagerRef,
+    ) -> SettingsHandler {
+        SettingsHandler {
+            config_manager,
+            health_manager,
+        }
     }
 }
 
 impl Handler for SettingsHandler {
     fn handle(&self, _req: &mut IromRequest) -> IronResult<IromResponse> {
         handle_empty(move || {
-            let indexes_available = self.config.indexes_path().is_some();
+            let config = self.config_manager.get();
+            let indexes_available = config.indexes_path().is_some();
             let mut destinations = Vec::new();
 
-            for (index, destination) in self.config.destinations().iter().enumerate() {
-                let name = format!(
-                    "{}@{}:{}",
-                    destination.role(),
-                    destination.host(),
-                    destination.port()
-                );
-                let destination = Destination::new(index, &name);
+            for (index, destination) in config.destinations().iter().enumerate() {
+                let health = self
+                    .health_manager
+                    .get(&config, index)
+                    .map_err(|_| HandlerError::new("Health manager error"))?;
+                let destination = Destination::new(index, destination, health);
 
                 destinations.push(destination);
             }
@@ -51,14 +61,26 @@ struct Response {
 #[derive(Debug, Clone, Serialize)]
 struct Destination {
     index: usize,
+    id: Option<String>,
     name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+    health: Option<DestinationHealth>,
 }
 
 impl Destination {
-    fn new(index: usize, name: &str) -> Destination {
+    fn new(
+        index: usize,
+        destination: &ConfigDestination,
+        health: Option<DestinationHealth>,
+    ) -> Destination {
         Destination {
             index,
-            name: name.into(),
+            id: destination.id().cloned(),
+            name: destination.display_name(),
+            description: destination.description().cloned(),
+            tags: destination.tags().to_vec(),
+            health,
         }
     }
 }
@@ -4,6 +4,7 @@ use super::HandlerResult;
 use crate::jobmanager::Job;
 use crate::jobmanager::JobManagerRef;
 use crate::jobmanager::JobStatus;
+use crate::launcher::RestoreRequest;
 use iron::middleware::Handler;
 use iron::IronResult;
 use iron::Request as IronRequest;
@@ -30,45 +31,88 @@ impl Handler for StatusHandler {
     fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
         handle_request(request, move |request: Request| {
             let jobid = request.jobid;
-            let (database_name, stage, stdout_path, stderr_path, status) = self
+            let job = self
                 .job_manager
                 .map_job(jobid, job_params)
                 .map_err(|_| HandlerError::new("Job manager error"))?
                 .ok_or_else(|| HandlerError::new("Job not found"))?;
+            let children = self.children_status(&job.children)?;
             let stdout_position = request.stdout_position.unwrap_or(0);
             let stderr_position = request.stderr_position.unwrap_or(0);
-            let (stdout, stdout_position) = read_file(&stdout_path, stdout_position)?;
-            let (stderr, stderr_position) = read_file(&stderr_path, stderr_position)?;
+            let (stdout, stdout_position) = read_file(&job.stdout_path, stdout_position)?;
+            let (stderr, stderr_position) = read_file(&job.stderr_path, stderr_position)?;
 
             Ok(Responce {
-                database_name,
-                stage,
+                database_name: job.database_name,
+                stage: job.stage,
                 stdout,
                 stdout_position,
                 stderr,
                 stderr_position,
-                status,
+                status: job.status,
+                children,
+                request: job.request,
             })
         })
     }
 }
 
-fn job_params(job: &Job) -> (String, String, PathBuf, PathBuf, Status) {
-    let database_name = job.database_name().into();
-    let stage = job
-        .stage()
-        .cloned()
-        .unwrap_or_else(|| String::with_capacity(0));
-    let stdout_path = job.stdout_path().into();
-    let stderr_path = job.stderr_path().into();
-    let status = match job.status() {
+impl StatusHandler {
+    /// Returns status of batch child jobs, jobs removed from job manager are skipped.
+    fn children_status(&self, children: &[usize]) -> HandlerResult<Vec<ChildStatus>> {
+        let mut result = Vec::with_capacity(children.len());
+
+        for &jobid in children {
+            let child = self
+                .job_manager
+                .map_job(jobid, |job| ChildStatus {
+                    jobid,
+                    database_name: job.database_name().into(),
+                    stage: job.stage().cloned(),
+                    status: status(job.status()),
+                })
+                .map_err(|_| HandlerError::new("Job manager error"))?;
+
+            result.extend(child);
+        }
+
+        Ok(result)
+    }
+}
+
+#[derive(Debug)]
+struct JobParams {
+    database_name: String,
+    stage: String,
+    stdout_path: PathBuf,
+    stderr_path: PathBuf,
+    status: Status,
+    children: Vec<usize>,
+    request: Option<RestoreRequest>,
+}
+
+fn job_params(job: &Job) -> JobParams {
+    JobParams {
+        database_name: job.database_name().into(),
+        stage: job
+            .stage()
+            .cloned()
+            .unwrap_or_else(|| String::with_capacity(0)),
+        stdout_path: job.stdout_path().into(),
+        stderr_path: job.stderr_path().into(),
+        status: status(job.status()),
+        children: job.children().into(),
+        request: job.request().cloned(),
+    }
+}
+
+fn status(status: &JobStatus) -> Status {
+    match status {
         JobStatus::Complete { success: true } => Status::Success,
         JobStatus::Complete { success: false } => Status::Failed,
         JobStatus::Aborted => Status::Aborted,
         _ => Status::InProgress,
-    };
-
-    (database_name, stage, stdout_path, stderr_path, status)
+    }
 }
 
 fn read_file(path: &Path, position: u64) -> HandlerResult<(String, u64)> {
@@ -118,6 +162,18 @@ struct Responce {
     stderr: String,
     stderr_position: u64,
     status: Status,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<ChildStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request: Option<RestoreRequest>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChildStatus {
+    jobid: usize,
+    database_name: String,
+    stage: Option<String>,
+    status: Status,
 }
 
 #[derive(Debug, Serialize)]
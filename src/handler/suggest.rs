@@ -0,0 +1,41 @@
+use super::util::handle_request;
+use super::HandlerError;
+use crate::pathmanager::PathManagerRef;
+use iron::middleware::Handler;
+use iron::IronResult;
+use iron::Request as IronRequest;
+use iron::Response as IronResponse;
+
+#[derive(Debug)]
+pub struct SuggestHandler {
+    path_manager: PathManagerRef,
+}
+
+impl SuggestHandler {
+    pub fn new(path_manager: PathManagerRef) -> SuggestHandler {
+        SuggestHandler { path_manager }
+    }
+}
+
+impl Handler for SuggestHandler {
+    fn handle(&self, request: &mut IronRequest) -> IronResult<IronResponse> {
+        handle_request(request, move |request: Request| {
+            let database_name = self
+                .path_manager
+                .infer_name(&request.path)
+                .map_err(|_| HandlerError::new("Failed to infer database name"))?;
+
+            Ok(Response { database_name })
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    database_name: Option<String>,
+}
@@ -9,7 +9,6 @@ use iron::Request;
 use iron::Response;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use serde_json;
 use std::io::Read;
 
 pub fn handle_empty<Res, F>(callback: F) -> IronResult<Response>
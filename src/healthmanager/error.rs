@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+pub type HealthManagerResult<T> = Result<T, HealthManagerError>;
+
+#[derive(Debug)]
+pub struct HealthManagerError {
+    message: String,
+}
+
+impl HealthManagerError {
+    pub fn new(message: &str) -> HealthManagerError {
+        HealthManagerError {
+            message: message.into(),
+        }
+    }
+}
+
+impl Error for HealthManagerError {}
+
+impl Display for HealthManagerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
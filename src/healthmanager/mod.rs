@@ -0,0 +1,225 @@
+mod error;
+
+pub use self::error::HealthManagerError;
+pub use self::error::HealthManagerResult;
+
+use crate::config::ConfigRef;
+use crate::config::Destination;
+use crate::configmanager::ConfigManagerRef;
+use crate::worker::PostgreSQL;
+use crate::worker::MAINTENANCE_DATABASE;
+use std::ffi::CString;
+use std::mem;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::thread::Builder;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone)]
+pub struct HealthManagerRef {
+    inner: Arc<RwLock<HealthManager>>,
+}
+
+impl HealthManagerRef {
+    fn with_read<F, T>(&self, callback: F) -> HealthManagerResult<T>
+    where
+        F: FnOnce(&HealthManager) -> HealthManagerResult<T>,
+    {
+        match self.inner.read() {
+            Ok(ref healthmanager) => callback(healthmanager),
+            Err(err) => {
+                warn!("Failed to acquire read lock - {}", err);
+
+                Err(HealthManagerError::new("Failed to acquire read lock"))
+            }
+        }
+    }
+
+    fn with_write<F, T>(&self, callback: F) -> HealthManagerResult<T>
+    where
+        F: FnOnce(&mut HealthManager) -> HealthManagerResult<T>,
+    {
+        match self.inner.write() {
+            Ok(ref mut healthmanager) => callback(healthmanager),
+            Err(err) => {
+                warn!("Failed to acquire write lock - {}", err);
+
+                Err(HealthManagerError::new("Failed to acquire write lock"))
+            }
+        }
+    }
+
+    /// Checks all destinations of `config` in parallel and returns health of every destination
+    /// in order of configuration. Results are stored only if configuration is not reloaded
+    /// meanwhile.
+    pub fn check(&self, config: &ConfigRef) -> HealthManagerResult<Vec<DestinationHealth>> {
+        let handles: Vec<_> = (0..config.destinations().len())
+            .map(|index| {
+                let config = config.clone();
+
+                Builder::new()
+                    .name(format!("health #{}", index))
+                    .spawn(move || check(&config.destinations()[index]))
+            })
+            .collect();
+        let result: Vec<_> = handles
+            .into_iter()
+            .map(|handle| match handle {
+                Ok(handle) => handle
+                    .join()
+                    .unwrap_or_else(|_| unavailable("Destination check failed".into())),
+                Err(err) => unavailable(format!("Failed to start destination check - {}", err)),
+            })
+            .collect();
+        let destinations = result.iter().cloned().map(Some).collect();
+
+        self.with_write(move |healthmanager| {
+            if Arc::ptr_eq(config, &healthmanager.config_manager.get()) {
+                healthmanager.config = Some(config.clone());
+                healthmanager.destinations = destinations;
+            }
+
+            Ok(())
+        })?;
+
+        Ok(result)
+    }
+
+    /// Returns result of last check of destination, `None` if destination is not checked yet
+    /// or results belong to configuration replaced by reload, destination indexes may differ.
+    pub fn get(
+        &self,
+        config: &ConfigRef,
+        index: usize,
+    ) -> HealthManagerResult<Option<DestinationHealth>> {
+        self.with_read(move |healthmanager| match healthmanager.config {
+            Some(ref checked) if Arc::ptr_eq(checked, config) => Ok(healthmanager
+                .destinations
+                .get(index)
+                .cloned()
+                .unwrap_or_default()),
+            _ => Ok(None),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct HealthManager {
+    config_manager: ConfigManagerRef,
+    config: Option<ConfigRef>,
+    destinations: Vec<Option<DestinationHealth>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DestinationHealth {
+    checked: i64,
+    available: bool,
+    version: Option<String>,
+    can_create_database: Option<bool>,
+    free_space: Option<u64>,
+    error: Option<String>,
+}
+
+fn check(destination: &Destination) -> DestinationHealth {
+    let checked = OffsetDateTime::now_utc().unix_timestamp();
+    let postgres = PostgreSQL::new(destination, MAINTENANCE_DATABASE);
+
+    match postgres.server_info() {
+        Ok(info) => DestinationHealth {
+            checked,
+            available: true,
+            version: Some(info.version().into()),
+            can_create_database: Some(info.can_create_database()),
+            free_space: info
+                .data_directory()
+                .filter(|_| is_local(destination.host()))
+                .and_then(|data_directory| free_space(data_directory)),
+            error: None,
+        },
+        Err(err) => unavailable(format!("{}", err)),
+    }
+}
+
+fn unavailable(error: String) -> DestinationHealth {
+    DestinationHealth {
+        checked: OffsetDateTime::now_utc().unix_timestamp(),
+        available: false,
+        version: None,
+        can_create_database: None,
+        free_space: None,
+        error: Some(error),
+    }
+}
+
+/// Checks if destination server runs on this host, so its data directory is accessible.
+fn is_local(host: &str) -> bool {
+    host.starts_with('/') || ["localhost", "127.0.0.1", "::1"].contains(&host)
+}
+
+/// Returns space available to unprivileged users on file system of `path` in bytes.
+#[allow(clippy::unnecessary_cast)]
+fn free_space(path: &str) -> Option<u64> {
+    let path = CString::new(path).ok()?;
+    // SAFETY: statvfs is plain data, zeroed value is valid.
+    let mut stat: libc::statvfs = unsafe { mem::zeroed() };
+
+    // SAFETY: path is null terminated and stat points to valid memory.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+fn log_health(destination: &Destination, health: &DestinationHealth) {
+    let name = destination.display_name();
+
+    match (&health.error, health.can_create_database) {
+        (Some(err), _) => warn!("Destination {} is not available - {}", name, err),
+        (None, Some(false)) => warn!("Destination {} role can not create databases", name),
+        _ => info!("Destination {} is available", name),
+    }
+}
+
+/// Checks destinations in background so unavailable servers do not delay startup.
+#[allow(clippy::needless_pass_by_value)]
+pub fn start(config: ConfigRef, health_manager: HealthManagerRef) {
+    if let Err(err) = Builder::new()
+        .name("health".to_string())
+        .spawn(move || match health_manager.check(&config) {
+            Ok(result) => {
+                for (destination, health) in config.destinations().iter().zip(&result) {
+                    log_health(destination, health);
+                }
+            }
+            Err(err) => warn!("Failed to check destinations - {}", err),
+        })
+    {
+        warn!("Failed to start destination check - {}", err);
+    }
+}
+
+pub fn create(config_manager: ConfigManagerRef) -> HealthManagerRef {
+    HealthManagerRef {
+        inner: Arc::new(RwLock::new(HealthManager {
+            config_manager,
+            config: None,
+            destinations: Vec::new(),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::free_space;
+    use super::is_local;
+
+    #[test]
+    fn free_space_shold_check_local_paths() {
+        assert!(is_local("localhost"));
+        assert!(is_local("/var/run/postgresql"));
+        assert!(!is_local("db.example.com"));
+        assert!(free_space(&std::env::temp_dir().to_string_lossy()).is_some());
+        assert!(free_space("/nonexistent/pgrestore").is_none());
+    }
+}
@@ -11,6 +11,7 @@ pub type HttpClientResult<T> = Result<T, HttpClientError>;
 #[derive(Debug)]
 pub struct HttpClientError {
     message: String,
+    retryable: bool,
 }
 
 impl HttpClientError {
@@ -18,8 +19,26 @@ impl HttpClientError {
     pub fn reqwest_error(error: ReqwestError) -> HttpClientError {
         warn!("Reqwest error - {}", error);
 
+        // Body errors are connection failures during transfer.
+        let retryable = error.is_connect()
+            || error.is_timeout()
+            || error.is_body()
+            || error
+                .status()
+                .is_some_and(|status| status.is_server_error());
+
         HttpClientError {
             message: format!("{}", error),
+            retryable,
+        }
+    }
+
+    pub fn invalid_proxy_url() -> HttpClientError {
+        warn!("Invalid proxy URL");
+
+        HttpClientError {
+            message: "Invalid proxy URL".into(),
+            retryable: false,
         }
     }
 
@@ -29,6 +48,7 @@ impl HttpClientError {
 
         HttpClientError {
             message: format!("{}", error),
+            retryable: false,
         }
     }
 
@@ -38,8 +58,15 @@ impl HttpClientError {
 
         HttpClientError {
             message: format!("{}", error),
+            retryable: false,
         }
     }
+
+    /// Checks if request may succeed if repeated: connection errors, timeouts and server
+    /// errors.
+    pub fn is_retryable(&self) -> bool {
+        self.retryable
+    }
 }
 
 impl Error for HttpClientError {}
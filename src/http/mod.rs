@@ -1,12 +1,18 @@
 mod error;
+mod proxy;
 
 pub use self::error::HttpClientError;
 pub use self::error::HttpClientResult;
 
+use self::proxy::NoProxy;
 use crate::config::ConfigRef;
 use reqwest::blocking::Client;
+use reqwest::blocking::Response;
 use reqwest::Certificate;
+use reqwest::Identity;
 use reqwest::IntoUrl;
+use reqwest::Proxy;
+use reqwest::Url;
 use std::fmt::Display;
 use std::fs;
 use std::fs::File;
@@ -47,19 +53,47 @@ impl HttpClient {
         for cetrificate_path in config.http_client().root_certificates() {
             debug!("Loading certificate {}", cetrificate_path);
 
-            let mut buffer = Vec::new();
-
-            File::open(cetrificate_path)
-                .map_err(HttpClientError::io_error)?
-                .read_to_end(&mut buffer)
-                .map_err(HttpClientError::io_error)?;
-
+            let buffer = read_file(cetrificate_path)?;
             let certificate =
                 Certificate::from_pem(&buffer).map_err(HttpClientError::reqwest_error)?;
 
             builder = builder.add_root_certificate(certificate);
         }
 
+        if let Some(client_certificate) = config.http_client().client_certificate() {
+            debug!(
+                "Loading client certificate {} with key {}",
+                client_certificate.certificate(),
+                client_certificate.key()
+            );
+
+            // Identity expects certificate chain and private key in the same PEM buffer.
+            let mut buffer = read_file(client_certificate.certificate())?;
+            buffer.push(b'\n');
+            buffer.extend(read_file(client_certificate.key())?);
+
+            let identity = Identity::from_pem(&buffer).map_err(HttpClientError::reqwest_error)?;
+
+            builder = builder.identity(identity);
+        }
+
+        if let Some(proxy) = config.http_client().proxy() {
+            let proxy_url =
+                Url::parse(proxy.url()).map_err(|_| HttpClientError::invalid_proxy_url())?;
+
+            debug!("Using proxy {}", proxy_url.host_str().unwrap_or_default());
+
+            let no_proxy = NoProxy::new(proxy.no_proxy());
+
+            builder = builder.proxy(Proxy::custom(move |url| {
+                if no_proxy.matches(url) {
+                    None
+                } else {
+                    Some(proxy_url.clone())
+                }
+            }));
+        }
+
         if config.http_client().accept_invalid_hostnames() {
             debug!("Accept invalid host names");
 
@@ -95,6 +129,7 @@ impl HttpClient {
             .client
             .get(url)
             .send()
+            .and_then(Response::error_for_status)
             .map_err(HttpClientError::reqwest_error)?;
         let mut writer = File::create(&file_path).map_err(HttpClientError::io_error)?;
         let result = PathHandle::new(file_path);
@@ -108,6 +143,17 @@ impl HttpClient {
     }
 }
 
+fn read_file(path: &str) -> HttpClientResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+
+    File::open(path)
+        .map_err(HttpClientError::io_error)?
+        .read_to_end(&mut buffer)
+        .map_err(HttpClientError::io_error)?;
+
+    Ok(buffer)
+}
+
 pub fn create(config: ConfigRef) -> HttpClientResult<HttpClientRef> {
     Ok(HttpClientRef {
         inner: Arc::new(Mutex::new(HttpClient::create(config)?)),
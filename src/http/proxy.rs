@@ -0,0 +1,83 @@
+use reqwest::Url;
+
+#[derive(Debug, Clone)]
+pub struct NoProxy {
+    hosts: Vec<String>,
+}
+
+impl NoProxy {
+    pub fn new<S>(hosts: &[S]) -> NoProxy
+    where
+        S: AsRef<str>,
+    {
+        NoProxy {
+            hosts: hosts
+                .iter()
+                .map(|host| host.as_ref().trim().to_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn matches(&self, url: &Url) -> bool {
+        match url.host_str() {
+            Some(host) => self.matches_host(host),
+            None => false,
+        }
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+
+        self.hosts.iter().any(|pattern| {
+            if pattern == "*" {
+                return true;
+            }
+
+            // Leading dot or star means "this domain and all subdomains".
+            let domain = pattern.trim_start_matches('*').trim_start_matches('.');
+
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoProxy;
+
+    #[test]
+    fn should_match_exact_host() {
+        let no_proxy = NoProxy::new(&["localhost", "10.0.0.1"]);
+
+        assert!(no_proxy.matches_host("localhost"));
+        assert!(no_proxy.matches_host("10.0.0.1"));
+        assert!(!no_proxy.matches_host("example.com"));
+    }
+
+    #[test]
+    fn should_match_subdomains() {
+        let no_proxy = NoProxy::new(&[".corp.local", "*.example.com", "internal"]);
+
+        assert!(no_proxy.matches_host("backups.corp.local"));
+        assert!(no_proxy.matches_host("corp.local"));
+        assert!(no_proxy.matches_host("files.example.com"));
+        assert!(no_proxy.matches_host("host.internal"));
+        assert!(!no_proxy.matches_host("notcorp.local"));
+    }
+
+    #[test]
+    fn should_match_everything_with_star() {
+        let no_proxy = NoProxy::new(&["*"]);
+
+        assert!(no_proxy.matches_host("example.com"));
+    }
+
+    #[test]
+    fn should_ignore_case_and_empty_entries() {
+        let no_proxy = NoProxy::new(&["", " Backups.Corp "]);
+
+        assert!(no_proxy.matches_host("BACKUPS.corp"));
+        assert!(!no_proxy.matches_host("example.com"));
+    }
+}
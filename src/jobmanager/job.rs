@@ -1,3 +1,4 @@
+use crate::launcher::RestoreRequest;
 use std::path::Path;
 use std::path::PathBuf;
 use time::OffsetDateTime;
@@ -11,10 +12,18 @@ pub struct Job {
     stage: Option<String>,
     stdout_path: PathBuf,
     stderr_path: PathBuf,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    request: Option<RestoreRequest>,
 }
 
 impl Job {
-    pub fn new(database_name: &str, stdout_path: &Path, stderr_path: &Path) -> Job {
+    pub fn new(
+        database_name: &str,
+        stdout_path: &Path,
+        stderr_path: &Path,
+        request: Option<RestoreRequest>,
+    ) -> Job {
         let created = OffsetDateTime::now_utc().unix_timestamp();
         let modified = created;
 
@@ -26,6 +35,9 @@ impl Job {
             stage: None,
             stdout_path: stdout_path.into(),
             stderr_path: stderr_path.into(),
+            parent: None,
+            children: Vec::new(),
+            request,
         }
     }
 
@@ -39,6 +51,14 @@ impl Job {
         self.stage = Some(stage.into());
     }
 
+    pub fn set_parent(&mut self, parent: usize) {
+        self.parent = Some(parent);
+    }
+
+    pub fn add_child(&mut self, child: usize) {
+        self.children.push(child);
+    }
+
     pub fn created(&self) -> i64 {
         self.created
     }
@@ -66,9 +86,25 @@ impl Job {
     pub fn stderr_path(&self) -> &Path {
         &self.stderr_path
     }
+
+    /// Batch job which started this job.
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Jobs started by batch job.
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// Request of restore job with resolved backup and database name, batch jobs have no
+    /// request.
+    pub fn request(&self) -> Option<&RestoreRequest> {
+        self.request.as_ref()
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum JobStatus {
     Pending,
     InProgress,
@@ -88,4 +124,11 @@ impl JobStatus {
     pub fn complete(success: bool) -> JobStatus {
         JobStatus::Complete { success }
     }
+
+    pub fn is_finished(&self) -> bool {
+        match self {
+            JobStatus::Pending | JobStatus::InProgress => false,
+            JobStatus::Aborted | JobStatus::Complete { .. } => true,
+        }
+    }
 }
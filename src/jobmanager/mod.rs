@@ -7,6 +7,7 @@ pub use self::job::Job;
 pub use self::job::JobStatus;
 
 use crate::config::ConfigRef;
+use crate::launcher::RestoreRequest;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -66,8 +67,28 @@ impl JobManagerRef {
         })
     }
 
-    pub fn next_jobid(&self, database_name: &str) -> JobManagerResult<usize> {
-        self.with_write(move |jobmanager| Ok(jobmanager.next_jobid(database_name)))
+    pub fn next_jobid(
+        &self,
+        database_name: &str,
+        request: Option<RestoreRequest>,
+    ) -> JobManagerResult<usize> {
+        self.with_write(move |jobmanager| Ok(jobmanager.next_jobid(database_name, request)))
+    }
+
+    /// Creates job started by batch job `parent`.
+    pub fn next_child_jobid(
+        &self,
+        parent: usize,
+        database_name: &str,
+        request: RestoreRequest,
+    ) -> JobManagerResult<usize> {
+        self.with_write(move |jobmanager| {
+            let jobid = jobmanager.next_jobid(database_name, Some(request));
+
+            jobmanager.set_parent(jobid, parent);
+
+            Ok(jobid)
+        })
     }
 
     pub fn set_stage(&self, jobid: usize, stage: &str) -> JobManagerResult<()> {
@@ -118,10 +139,7 @@ impl JobManager {
     where
         F: FnOnce(&Job) -> T,
     {
-        match self.jobs.get(&jobid) {
-            Some(job) => Some(callback(job)),
-            None => None,
-        }
+        self.jobs.get(&jobid).map(callback)
     }
 
     pub fn for_each<F>(&self, mut callback: F)
@@ -133,14 +151,14 @@ impl JobManager {
         }
     }
 
-    fn next_jobid(&mut self, database_name: &str) -> usize {
+    fn next_jobid(&mut self, database_name: &str, request: Option<RestoreRequest>) -> usize {
         self.last_jobid += 1;
 
         let (stdout_path, stderr_path) = prepare_job_logs(&self.joblogs_path, self.last_jobid);
 
         self.jobs.insert(
             self.last_jobid,
-            Job::new(database_name, &stdout_path, &stderr_path),
+            Job::new(database_name, &stdout_path, &stderr_path, request),
         );
 
         if self.last_jobid > self.max_jobs {
@@ -156,11 +174,25 @@ impl JobManager {
         self.last_jobid
     }
 
+    fn set_parent(&mut self, jobid: usize, parent: usize) {
+        if let Some(job) = self.jobs.get_mut(&jobid) {
+            job.set_parent(parent);
+        }
+
+        if let Some(job) = self.jobs.get_mut(&parent) {
+            job.add_child(jobid);
+        }
+    }
+
     fn set_stage(&mut self, jobid: usize, stage: &str) {
         if let Some(job) = self.jobs.get_mut(&jobid) {
             debug!("Set job {} stage: {}", jobid, stage);
 
-            job.set_status(JobStatus::in_progress());
+            // Keep status of aborted job, worker will notice it before next command.
+            if job.status() == &JobStatus::Pending {
+                job.set_status(JobStatus::in_progress());
+            }
+
             job.set_stage(stage);
         }
     }
@@ -180,6 +212,12 @@ impl JobManager {
 
     fn set_complete(&mut self, jobid: usize, success: bool) {
         if let Some(job) = self.jobs.get_mut(&jobid) {
+            if job.status() == &JobStatus::Aborted {
+                debug!("Job {} already aborted", jobid);
+
+                return;
+            }
+
             debug!("Set job {} complete with {}", jobid, success);
 
             job.set_status(JobStatus::complete(success));
@@ -202,7 +240,7 @@ fn remove_job_output(path: &Path) {
         debug!("Removing job output {}", path.display());
 
         if path.is_file() {
-            if let Err(err) = fs::remove_file(&path) {
+            if let Err(err) = fs::remove_file(path) {
                 warn!(
                     "Failed to remove job output path {} - {}",
                     path.display(),
@@ -0,0 +1,211 @@
+use super::Launcher;
+use super::LauncherError;
+use super::LauncherResult;
+use super::Restore;
+use crate::jobmanager::JobManagerRef;
+use crate::jobmanager::JobStatus;
+use std::collections::VecDeque;
+use std::thread;
+use std::thread::Builder;
+use std::time::Duration;
+
+/// Interval between checks of child jobs, the same as used by restore commands.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+struct Batch {
+    launcher: Launcher,
+    batch_id: usize,
+    pending: VecDeque<(usize, Restore)>,
+    running: Vec<usize>,
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    unknown: usize,
+    max_parallel: usize,
+    abort_on_failure: bool,
+    stage: String,
+}
+
+impl Batch {
+    fn job_manager(&self) -> &JobManagerRef {
+        &self.launcher.job_manager
+    }
+
+    /// Returns status of job or `None` if job was removed from job manager.
+    fn status(&self, job_id: usize) -> Option<JobStatus> {
+        self.job_manager()
+            .map_job(job_id, |job| job.status().clone())
+            .unwrap_or_default()
+    }
+
+    fn run(mut self) {
+        loop {
+            if self.status(self.batch_id) == Some(JobStatus::Aborted) {
+                info!("Batch job {} aborted", self.batch_id);
+                self.abort_children();
+
+                return;
+            }
+
+            self.collect_finished();
+
+            if self.failed > 0 && self.abort_on_failure {
+                warn!("Batch job {} failed, aborting other jobs", self.batch_id);
+                self.abort_children();
+                self.update_stage();
+                self.set_complete(false);
+
+                return;
+            }
+
+            self.start_pending();
+            self.update_stage();
+
+            if self.pending.is_empty() && self.running.is_empty() {
+                self.set_complete(self.failed == 0 && self.unknown == 0);
+
+                return;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Reports aggregate progress of child jobs as stage of batch job.
+    fn update_stage(&mut self) {
+        let mut stage = format!(
+            "{} of {} complete, {} running, {} failed",
+            self.succeeded + self.failed + self.unknown,
+            self.total,
+            self.running.len(),
+            self.failed
+        );
+
+        if self.unknown > 0 {
+            stage.push_str(&format!(", {} unknown", self.unknown));
+        }
+
+        if stage != self.stage {
+            if let Err(err) = self.job_manager().set_stage(self.batch_id, &stage) {
+                warn!("Failed to set batch job {} stage - {}", self.batch_id, err);
+            }
+
+            self.stage = stage;
+        }
+    }
+
+    /// Removes finished jobs from running, aborted jobs counted as failed. Jobs removed from job
+    /// manager by `max_jobs` limit have unknown result, they are not counted as failed and do not
+    /// abort other jobs of batch.
+    fn collect_finished(&mut self) {
+        let statuses: Vec<_> = self
+            .running
+            .iter()
+            .map(|&job_id| (job_id, self.status(job_id)))
+            .collect();
+
+        for (job_id, status) in statuses {
+            match status {
+                Some(JobStatus::Complete { success: true }) => self.succeeded += 1,
+                Some(ref status) if !status.is_finished() => continue,
+                Some(_) => self.failed += 1,
+                None => {
+                    warn!("Job {} of batch was removed, result is unknown", job_id);
+                    self.unknown += 1;
+                }
+            }
+
+            self.running.retain(|&running_id| running_id != job_id);
+        }
+    }
+
+    fn start_pending(&mut self) {
+        while self.running.len() < self.max_parallel {
+            let (job_id, restore) = match self.pending.pop_front() {
+                Some(child) => child,
+                None => break,
+            };
+
+            // Child job may be aborted by user or removed before it was started.
+            match self.status(job_id) {
+                Some(JobStatus::Pending) => (),
+                Some(_) => {
+                    self.failed += 1;
+
+                    continue;
+                }
+                None => {
+                    warn!("Job {} of batch was removed before start", job_id);
+                    self.unknown += 1;
+
+                    continue;
+                }
+            }
+
+            match self.launcher.start(job_id, restore) {
+                Ok(()) => self.running.push(job_id),
+                Err(err) => {
+                    warn!("Failed to start job {} of batch - {}", job_id, err);
+
+                    if let Err(err) = self.job_manager().set_complete(job_id, false) {
+                        warn!("Failed to set job {} status - {}", job_id, err);
+                    }
+
+                    self.failed += 1;
+                }
+            }
+        }
+    }
+
+    fn abort_children(&mut self) {
+        let children = self
+            .running
+            .drain(..)
+            .chain(self.pending.drain(..).map(|(job_id, _)| job_id));
+
+        for job_id in children {
+            if let Err(err) = self.launcher.job_manager.set_aborted(job_id) {
+                warn!("Failed to abort job {} - {}", job_id, err);
+            }
+        }
+    }
+
+    fn set_complete(&self, success: bool) {
+        info!("Batch job {} complete with {}", self.batch_id, success);
+
+        if let Err(err) = self.job_manager().set_complete(self.batch_id, success) {
+            warn!("Failed to set batch job {} status - {}", self.batch_id, err);
+        }
+    }
+}
+
+/// Starts thread which runs child jobs of batch, at most `max_parallel` at the same time.
+pub(super) fn start(
+    launcher: Launcher,
+    batch_id: usize,
+    children: Vec<(usize, Restore)>,
+    max_parallel: usize,
+    abort_on_failure: bool,
+) -> LauncherResult<()> {
+    let batch = Batch {
+        launcher,
+        batch_id,
+        total: children.len(),
+        pending: children.into_iter().collect(),
+        running: Vec::new(),
+        succeeded: 0,
+        failed: 0,
+        unknown: 0,
+        max_parallel,
+        abort_on_failure,
+        stage: String::new(),
+    };
+
+    Builder::new()
+        .name(format!("batch worker #{}", batch_id))
+        .spawn(move || batch.run())
+        .map_err(LauncherError::spawn_thread_error)?;
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use std::error::Error;
+use std::fmt::Arguments;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::io::Error as IoError;
+
+pub type LauncherResult<T> = Result<T, LauncherError>;
+
+#[derive(Debug)]
+pub struct LauncherError {
+    message: String,
+}
+
+impl LauncherError {
+    pub fn new(message: &str) -> LauncherError {
+        LauncherError {
+            message: message.into(),
+        }
+    }
+
+    pub fn format(args: Arguments) -> LauncherError {
+        LauncherError {
+            message: format!("{}", args),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn spawn_thread_error(error: IoError) -> LauncherError {
+        warn!("Failed to spawn thread - {}", error);
+
+        LauncherError {
+            message: format!("Failed to spawn thread - {}", error),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for LauncherError {}
+
+impl Display for LauncherError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
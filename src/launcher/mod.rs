@@ -0,0 +1,477 @@
+mod batch;
+mod error;
+
+pub use self::error::LauncherError;
+pub use self::error::LauncherResult;
+
+use crate::config::Config;
+use crate::config::ConfigRef;
+use crate::config::Destination;
+use crate::config::DestinationRef;
+use crate::configmanager::ConfigManagerRef;
+use crate::expirymanager::ExpiryManagerRef;
+use crate::http::HttpClientRef;
+use crate::jobmanager::JobManagerRef;
+use crate::pathmanager::PathManagerRef;
+use crate::worker::RestoreWorker;
+use glob::Pattern;
+
+/// Starts restore jobs, shared by HTTP handlers and scheduler.
+#[derive(Debug, Clone)]
+pub struct Launcher {
+    config_manager: ConfigManagerRef,
+    job_manager: JobManagerRef,
+    path_manager: PathManagerRef,
+    http_client: HttpClientRef,
+    expiry_manager: ExpiryManagerRef,
+}
+
+impl Launcher {
+    pub fn new(
+        config_manager: ConfigManagerRef,
+        job_manager: JobManagerRef,
+        path_manager: PathManagerRef,
+        http_client: HttpClientRef,
+        expiry_manager: ExpiryManagerRef,
+    ) -> Launcher {
+        Launcher {
+            config_manager,
+            job_manager,
+            path_manager,
+            http_client,
+            expiry_manager,
+        }
+    }
+
+    /// Checks request parameters which do not depend on backup index, returns request with
+    /// destination reference which does not depend on order of destinations.
+    pub fn validate(&self, request: RestoreRequest) -> LauncherResult<RestoreRequest> {
+        let config = self.config_manager.get();
+        let request = request
+            .with_canonical_destination(&config)
+            .ok_or_else(|| LauncherError::new("Invalid destination id"))?;
+
+        match request.ttl {
+            Some(_) if config.expiry().is_none() => {
+                return Err(LauncherError::new("Database expiry is not configured"))
+            }
+            Some(_) if !self.expiry_manager.is_enabled() => {
+                return Err(LauncherError::new(
+                    "Database expiry is configured by reload, restart is required",
+                ))
+            }
+            Some(0) => return Err(LauncherError::new("TTL must be positive")),
+            Some(_) if matches!(request.database, DatabaseType::Exists) => {
+                return Err(LauncherError::new(
+                    "TTL is allowed only for created databases",
+                ))
+            }
+            _ => {}
+        }
+
+        match request.backup {
+            Backup::Path { ref path } if path.is_empty() => {
+                Err(LauncherError::new("Backup path must not be empty"))
+            }
+            Backup::Url { ref url } if url.is_empty() => {
+                Err(LauncherError::new("Backup URL must not be empty"))
+            }
+            Backup::Latest { ref database } if database.is_empty() => {
+                Err(LauncherError::new("Backup database must not be empty"))
+            }
+            Backup::LatestMatching { ref pattern } if pattern.is_empty() => {
+                Err(LauncherError::new("Backup pattern must not be empty"))
+            }
+            Backup::LatestMatching { ref pattern } if Pattern::new(pattern).is_err() => {
+                Err(LauncherError::new("Invalid backup pattern"))
+            }
+            _ => Ok(()),
+        }?;
+
+        Ok(request)
+    }
+
+    /// Validates request, resolves backup and database name and starts restore job. Returns
+    /// id of started job.
+    pub fn launch(&self, request: RestoreRequest) -> LauncherResult<usize> {
+        let request = self.validate(request)?;
+        let restore = self.resolve(request.clone())?;
+        let job_id = self
+            .job_manager
+            .next_jobid(
+                &restore.database_name,
+                Some(restore.resolved_request(request)),
+            )
+            .map_err(|_| LauncherError::new("Failed to create job"))?;
+
+        self.start(job_id, restore)?;
+
+        Ok(job_id)
+    }
+
+    /// Validates all requests before creating any job, then starts batch job which runs
+    /// restores as child jobs. Returns id of batch job.
+    pub fn launch_batch(&self, request: BatchRequest) -> LauncherResult<usize> {
+        if request.restores.is_empty() {
+            return Err(LauncherError::new(
+                "Batch must contain at least one restore",
+            ));
+        }
+
+        if request.max_parallel == Some(0) {
+            return Err(LauncherError::new(
+                "Maximal parallel restores must be positive",
+            ));
+        }
+
+        let mut restores = Vec::with_capacity(request.restores.len());
+
+        for (index, request) in request.restores.into_iter().enumerate() {
+            let (request, restore) = self
+                .validate(request)
+                .and_then(|request| Ok((request.clone(), self.resolve(request)?)))
+                .map_err(|err| {
+                    LauncherError::format(format_args!("Restore {} - {}", index + 1, err))
+                })?;
+
+            restores.push((request, restore));
+        }
+
+        let name = request.name.unwrap_or_else(|| "batch".into());
+        let max_parallel = match (
+            request.max_parallel,
+            self.config_manager.get().max_batch_parallel(),
+        ) {
+            (Some(requested), Some(limit)) => requested.min(limit),
+            (Some(requested), None) => requested,
+            (None, Some(limit)) => limit,
+            (None, None) => restores.len(),
+        };
+        let batch_id = self
+            .job_manager
+            .next_jobid(&name, None)
+            .map_err(|_| LauncherError::new("Failed to create job"))?;
+        let mut children = Vec::with_capacity(restores.len());
+
+        for (request, restore) in restores {
+            let job_id = self
+                .job_manager
+                .next_child_jobid(
+                    batch_id,
+                    &restore.database_name,
+                    restore.resolved_request(request),
+                )
+                .map_err(|_| LauncherError::new("Failed to create job"))?;
+
+            children.push((job_id, restore));
+        }
+
+        batch::start(
+            self.clone(),
+            batch_id,
+            children,
+            max_parallel,
+            request.abort_on_failure,
+        )?;
+
+        Ok(batch_id)
+    }
+
+    /// Starts new job with request of finished job, the same backup is restored into the same
+    /// database. Returns id of started job.
+    pub fn rerun(&self, job_id: usize) -> LauncherResult<usize> {
+        let (finished, request) = self
+            .job_manager
+            .map_job(job_id, |job| {
+                (job.status().is_finished(), job.request().cloned())
+            })
+            .map_err(|_| LauncherError::new("Job manager error"))?
+            .ok_or_else(|| LauncherError::new("Job not found"))?;
+
+        if !finished {
+            return Err(LauncherError::new("Job is not finished"));
+        }
+
+        let request = request.ok_or_else(|| LauncherError::new("Batch job can not be re-run"))?;
+
+        info!("Re-running job {}", job_id);
+
+        self.launch(request)
+    }
+
+    /// Resolves backup source and database name of validated request. Restore keeps current
+    /// configuration, so job is not affected by configuration reload.
+    fn resolve(&self, request: RestoreRequest) -> LauncherResult<Restore> {
+        let config = self.config_manager.get();
+        let (destination_index, destination) = config
+            .find_destination(&request.destination)
+            .map(|(index, destination)| (index, destination.clone()))
+            .ok_or_else(|| LauncherError::new("Invalid destination id"))?;
+        let source = self.backup_source(request.backup)?;
+        let database_name = match request.database_name {
+            Some(database_name) => database_name,
+            None => self.infer_name(&source)?,
+        };
+
+        if database_name.is_empty() {
+            return Err(LauncherError::new("Database name must not be empty"));
+        }
+
+        if matches!(request.database, DatabaseType::DropAndCreate)
+            && config.is_protected_database(&database_name)
+        {
+            return Err(LauncherError::format(format_args!(
+                "Database {} is protected",
+                database_name
+            )));
+        }
+
+        let terminate_connections = request
+            .terminate_connections
+            .unwrap_or_else(|| destination.terminate_connections());
+
+        Ok(Restore {
+            config,
+            destination,
+            source,
+            database_name,
+            database: request.database,
+            restore: request.restore,
+            ignore_errors: request.ignore_errors,
+            terminate_connections,
+            destination_index,
+            ttl: request.ttl,
+        })
+    }
+
+    /// Starts restore worker for already created job.
+    fn start(&self, job_id: usize, restore: Restore) -> LauncherResult<()> {
+        let create_database = match restore.database {
+            DatabaseType::Exists => false,
+            DatabaseType::DropAndCreate => true,
+        };
+        let mut worker = RestoreWorker::new(
+            restore.config.clone(),
+            self.job_manager.clone(),
+            &restore.destination,
+            restore.database_name.as_ref(),
+            restore.ignore_errors,
+            restore.terminate_connections,
+        );
+
+        if create_database {
+            worker = worker.with_expiry(
+                self.expiry_manager.clone(),
+                restore.config.destination_ref(restore.destination_index),
+                restore.ttl,
+            );
+        }
+
+        match (restore.restore, restore.source) {
+            (RestoreType::Full, BackupSource::Path(path)) => worker
+                .restore_file_full(job_id, path.as_ref(), create_database, create_database)
+                .map_err(|err| LauncherError::new(err.message()))?,
+            (
+                RestoreType::Partial {
+                    objects,
+                    restore_schema,
+                    restore_indexes,
+                },
+                BackupSource::Path(path),
+            ) => worker
+                .restore_file_partial(
+                    job_id,
+                    path.as_ref(),
+                    &objects,
+                    restore_schema,
+                    restore_indexes,
+                    create_database,
+                    create_database,
+                )
+                .map_err(|err| LauncherError::new(err.message()))?,
+            (RestoreType::Full, BackupSource::Url(url)) => worker
+                .restore_url_full(
+                    job_id,
+                    &url,
+                    self.http_client.clone(),
+                    create_database,
+                    create_database,
+                )
+                .map_err(|err| LauncherError::new(err.message()))?,
+            (
+                RestoreType::Partial {
+                    objects,
+                    restore_schema,
+                    restore_indexes,
+                },
+                BackupSource::Url(url),
+            ) => worker
+                .restore_url_partial(
+                    job_id,
+                    &url,
+                    self.http_client.clone(),
+                    &objects,
+                    restore_schema,
+                    restore_indexes,
+                    create_database,
+                    create_database,
+                )
+                .map_err(|err| LauncherError::new(err.message()))?,
+        }
+
+        Ok(())
+    }
+
+    fn backup_source(&self, backup: Backup) -> LauncherResult<BackupSource> {
+        let entry = match backup {
+            Backup::Path { path } => return Ok(BackupSource::Path(path)),
+            Backup::Url { url } => return Ok(BackupSource::Url(url)),
+            Backup::Latest { database } => self
+                .path_manager
+                .latest_backup(&database)
+                .map_err(|_| LauncherError::new("Failed to query backups"))?
+                .ok_or_else(|| LauncherError::new("No backups found for database"))?,
+            Backup::LatestMatching { pattern } => {
+                let pattern = Pattern::new(&pattern)
+                    .map_err(|_| LauncherError::new("Invalid backup pattern"))?;
+
+                self.path_manager
+                    .latest_matching(&pattern)
+                    .map_err(|_| LauncherError::new("Failed to query backups"))?
+                    .ok_or_else(|| LauncherError::new("No backups found for pattern"))?
+            }
+        };
+
+        Ok(BackupSource::Path(entry.path().display().to_string()))
+    }
+
+    fn infer_name(&self, source: &BackupSource) -> LauncherResult<String> {
+        let path = match source {
+            BackupSource::Path(path) => path,
+            BackupSource::Url(url) => url,
+        };
+
+        self.path_manager
+            .infer_name(path)
+            .map_err(|_| LauncherError::new("Failed to infer database name"))?
+            .ok_or_else(|| LauncherError::new("Database name is not given and can not be inferred"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreRequest {
+    destination: DestinationRef,
+    backup: Backup,
+    #[serde(default)]
+    database_name: Option<String>,
+    database: DatabaseType,
+    restore: RestoreType,
+    ignore_errors: bool,
+    #[serde(default)]
+    terminate_connections: Option<bool>,
+    #[serde(default)]
+    ttl: Option<u64>,
+}
+
+impl RestoreRequest {
+    pub fn destination(&self) -> &DestinationRef {
+        &self.destination
+    }
+
+    /// Replaces destination by reference which does not depend on order of destinations,
+    /// returns `None` if destination does not exist.
+    pub fn with_canonical_destination(mut self, config: &Config) -> Option<RestoreRequest> {
+        self.destination = config.canonical_destination(&self.destination)?;
+
+        Some(self)
+    }
+
+    /// Replaces backup source and database name if given.
+    pub fn with_overrides(
+        mut self,
+        backup: Option<Backup>,
+        database_name: Option<String>,
+    ) -> RestoreRequest {
+        if let Some(backup) = backup {
+            self.backup = backup;
+        }
+
+        if database_name.is_some() {
+            self.database_name = database_name;
+        }
+
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Backup {
+    Path { path: String },
+    Url { url: String },
+    Latest { database: String },
+    LatestMatching { pattern: String },
+}
+
+/// Batch of restores started by single request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRequest {
+    #[serde(default)]
+    name: Option<String>,
+    restores: Vec<RestoreRequest>,
+    #[serde(default)]
+    max_parallel: Option<usize>,
+    #[serde(default)]
+    abort_on_failure: bool,
+}
+
+/// Request with resolved backup source and database name, ready to start.
+#[derive(Debug)]
+struct Restore {
+    config: ConfigRef,
+    destination: Destination,
+    source: BackupSource,
+    database_name: String,
+    database: DatabaseType,
+    restore: RestoreType,
+    ignore_errors: bool,
+    terminate_connections: bool,
+    destination_index: usize,
+    ttl: Option<u64>,
+}
+
+impl Restore {
+    /// Returns request with resolved backup source and database name, so re-run of job
+    /// restores the same backup even if newer backup is found.
+    fn resolved_request(&self, request: RestoreRequest) -> RestoreRequest {
+        let backup = match self.source {
+            BackupSource::Path(ref path) => Backup::Path { path: path.clone() },
+            BackupSource::Url(ref url) => Backup::Url { url: url.clone() },
+        };
+
+        request.with_overrides(Some(backup), Some(self.database_name.clone()))
+    }
+}
+
+#[derive(Debug)]
+enum BackupSource {
+    Path(String),
+    Url(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DatabaseType {
+    Exists,
+    DropAndCreate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RestoreType {
+    Full,
+    Partial {
+        objects: Vec<String>,
+        restore_schema: bool,
+        restore_indexes: bool,
+    },
+}
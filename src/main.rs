@@ -5,12 +5,20 @@ extern crate log;
 extern crate serde_derive;
 
 mod config;
+mod configmanager;
+mod databasemanager;
 mod error;
+mod expirymanager;
 mod handler;
+mod healthmanager;
 mod http;
 mod jobmanager;
+mod launcher;
 mod options;
 mod pathmanager;
+mod presetmanager;
+mod scanmanager;
+mod scheduler;
 mod server;
 mod worker;
 
@@ -21,6 +29,7 @@ use structopt::StructOpt;
 
 fn main() -> ApplicationResult {
     env_logger::init();
+    configmanager::block_reload_signal();
 
     let options = Options::from_args();
     let config =
@@ -28,12 +37,57 @@ fn main() -> ApplicationResult {
 
     config::validate(config.clone()).map_err(ApplicationError::config_error)?;
 
-    let path_manager = pathmanager::create();
+    let config_manager = configmanager::create(options.config_path(), config.clone());
+    let path_manager = pathmanager::create(config.clone());
     let http_client = http::create(config.clone()).map_err(ApplicationError::http_client_error)?;
     let job_manager = jobmanager::create(config.clone());
+    let scan_manager = scanmanager::create(config.clone());
+    let scheduler = scheduler::create(config.clone());
+    let preset_manager = presetmanager::create(config.clone());
+    let health_manager = healthmanager::create(config_manager.clone());
+    let database_manager =
+        databasemanager::DatabaseManager::new(config_manager.clone(), job_manager.clone());
+    let expiry_manager = expirymanager::create(config.clone());
+    let launcher = launcher::Launcher::new(
+        config_manager.clone(),
+        job_manager.clone(),
+        path_manager.clone(),
+        http_client,
+        expiry_manager.clone(),
+    );
 
-    worker::start_search(config.clone(), path_manager.clone());
-    server::start(&options, config, job_manager, path_manager, http_client);
+    worker::start_search(config.clone(), path_manager.clone(), scan_manager.clone());
+    scheduler::start(scheduler.clone(), launcher.clone());
+    healthmanager::start(config.clone(), health_manager.clone());
+    expirymanager::start(
+        config_manager.clone(),
+        expiry_manager.clone(),
+        database_manager.clone(),
+    );
+
+    let reloader = configmanager::Reloader::new(
+        config_manager.clone(),
+        path_manager.clone(),
+        scan_manager.clone(),
+        scheduler.clone(),
+        health_manager.clone(),
+    );
+
+    configmanager::start(reloader.clone());
+    server::start(
+        &options,
+        config_manager,
+        job_manager,
+        path_manager,
+        scan_manager,
+        scheduler,
+        preset_manager,
+        health_manager,
+        database_manager,
+        expiry_manager,
+        launcher,
+        reloader,
+    );
 
     Ok(())
 }
@@ -0,0 +1,247 @@
+use super::BackupFormat;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::Read;
+use std::io::Result as IoResult;
+use std::path::Path;
+use time::Date;
+use time::Month;
+use time::PrimitiveDateTime;
+use time::Time;
+
+const HEADER_SIZE: u64 = 1024;
+const CUSTOM_MAGIC: &[u8] = b"PGDMP";
+const TAR_MAGIC: &[u8] = b"ustar";
+const TAR_MAGIC_OFFSET: usize = 257;
+const TAR_BLOCK_SIZE: usize = 512;
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const PLAIN_MAGIC: &[u8] = b"--\n-- PostgreSQL database dump";
+
+pub fn read_header(path: &Path) -> IoResult<(BackupFormat, Option<i64>)> {
+    let mut buffer = Vec::with_capacity(HEADER_SIZE as usize);
+
+    File::open(path)?
+        .take(HEADER_SIZE)
+        .read_to_end(&mut buffer)?;
+
+    Ok(parse_header(&buffer))
+}
+
+fn parse_header(buffer: &[u8]) -> (BackupFormat, Option<i64>) {
+    if buffer.starts_with(CUSTOM_MAGIC) {
+        (BackupFormat::Custom, parse_dump_timestamp(buffer))
+    } else if buffer.get(TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + TAR_MAGIC.len()) == Some(TAR_MAGIC) {
+        // First member of tar archive is `toc.dat` which starts with custom format header.
+        let timestamp = buffer
+            .get(TAR_BLOCK_SIZE..)
+            .filter(|toc| toc.starts_with(CUSTOM_MAGIC))
+            .and_then(parse_dump_timestamp);
+
+        (BackupFormat::Tar, timestamp)
+    } else if buffer.starts_with(GZIP_MAGIC) {
+        (BackupFormat::Gzip, None)
+    } else if buffer.starts_with(PLAIN_MAGIC) {
+        (BackupFormat::Plain, None)
+    } else {
+        (BackupFormat::Unknown, None)
+    }
+}
+
+/// Reads dump creation time from archive header (see `ReadHead` in `pg_backup_archiver.c`).
+/// Time stored in header has no time zone, so it treated as UTC.
+fn parse_dump_timestamp(buffer: &[u8]) -> Option<i64> {
+    let mut reader = HeaderReader::new(buffer);
+
+    reader.skip(CUSTOM_MAGIC.len())?;
+
+    let major = reader.read_byte()?;
+    let minor = reader.read_byte()?;
+    let revision = if major > 1 || (major == 1 && minor > 0) {
+        reader.read_byte()?
+    } else {
+        0
+    };
+    let version = (major, minor, revision);
+
+    // Archives before 1.7 have different header layout.
+    if version < (1, 7, 0) {
+        return None;
+    }
+
+    let int_size = reader.read_byte()?;
+
+    reader.set_int_size(int_size)?;
+    reader.skip(2)?; // offset size and format
+
+    if version >= (1, 15, 0) {
+        reader.skip(1)?; // compression algorithm
+    } else {
+        reader.read_int()?; // compression level
+    }
+
+    let second = reader.read_int()?;
+    let minute = reader.read_int()?;
+    let hour = reader.read_int()?;
+    let day = reader.read_int()?;
+    let month = reader.read_int()?;
+    let year = reader.read_int()?;
+
+    let month = Month::try_from(u8::try_from(month + 1).ok()?).ok()?;
+    let date = Date::from_calendar_date(
+        i32::try_from(year + 1900).ok()?,
+        month,
+        u8::try_from(day).ok()?,
+    )
+    .ok()?;
+    let time = Time::from_hms(
+        u8::try_from(hour).ok()?,
+        u8::try_from(minute).ok()?,
+        u8::try_from(second.min(59)).ok()?,
+    )
+    .ok()?;
+
+    Some(
+        PrimitiveDateTime::new(date, time)
+            .assume_utc()
+            .unix_timestamp(),
+    )
+}
+
+#[derive(Debug)]
+struct HeaderReader<'a> {
+    buffer: &'a [u8],
+    position: usize,
+    int_size: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+    fn new(buffer: &'a [u8]) -> HeaderReader<'a> {
+        HeaderReader {
+            buffer,
+            position: 0,
+            int_size: 4,
+        }
+    }
+
+    fn set_int_size(&mut self, int_size: u8) -> Option<()> {
+        if int_size > 0 && int_size <= 8 {
+            self.int_size = int_size as usize;
+
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn skip(&mut self, n: usize) -> Option<()> {
+        if self.position + n <= self.buffer.len() {
+            self.position += n;
+
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        let result = self.buffer.get(self.position).cloned();
+
+        self.position += 1;
+
+        result
+    }
+
+    /// Reads sign byte followed by `int_size` bytes of absolute value, least significant first.
+    fn read_int(&mut self) -> Option<i64> {
+        let sign = self.read_byte()?;
+        let mut value: i64 = 0;
+
+        for index in 0..self.int_size {
+            value |= i64::from(self.read_byte()?) << (8 * index);
+        }
+
+        if sign == 0 {
+            Some(value)
+        } else {
+            Some(-value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_header;
+    use super::BackupFormat;
+
+    fn custom_header(minor: u8, compression: &[u8], time: &[u32]) -> Vec<u8> {
+        let mut result = b"PGDMP".to_vec();
+
+        result.extend(&[1, minor, 0, 4, 8, 1]);
+        result.extend(compression);
+
+        for &value in time {
+            result.push(0);
+            result.extend(&value.to_le_bytes());
+        }
+
+        result.extend(b"\x00\x00\x00\x00\x00\x08database");
+        result
+    }
+
+    #[test]
+    fn should_parse_custom_header_timestamp() {
+        // 2021-03-04 05:06:07
+        let header = custom_header(14, &[0, 0xff, 0xff, 0xff, 0xff], &[7, 6, 5, 4, 2, 121, 0]);
+
+        assert_eq!(
+            (BackupFormat::Custom, Some(1_614_834_367)),
+            parse_header(&header)
+        );
+    }
+
+    #[test]
+    fn should_parse_compression_algorithm_byte() {
+        // 2023-12-31 23:59:59
+        let header = custom_header(15, &[1], &[59, 59, 23, 31, 11, 123, 0]);
+
+        assert_eq!(
+            (BackupFormat::Custom, Some(1_704_067_199)),
+            parse_header(&header)
+        );
+    }
+
+    #[test]
+    fn should_detect_tar_archive() {
+        let mut header = vec![0; 512];
+
+        header[..7].copy_from_slice(b"toc.dat");
+        header[257..262].copy_from_slice(b"ustar");
+        header.extend(custom_header(
+            14,
+            &[0, 0, 0, 0, 0],
+            &[0, 0, 0, 1, 0, 100, 0],
+        ));
+
+        assert_eq!(
+            (BackupFormat::Tar, Some(946_684_800)),
+            parse_header(&header)
+        );
+    }
+
+    #[test]
+    fn should_detect_other_formats() {
+        assert_eq!(
+            (BackupFormat::Plain, None),
+            parse_header(b"--\n-- PostgreSQL database dump\n--\n")
+        );
+        assert_eq!((BackupFormat::Gzip, None), parse_header(&[0x1f, 0x8b, 8]));
+        assert_eq!((BackupFormat::Unknown, None), parse_header(b"PGDM"));
+    }
+
+    #[test]
+    fn should_ignore_truncated_header() {
+        let header = custom_header(14, &[0, 0, 0, 0, 0], &[7, 6, 5]);
+
+        assert_eq!((BackupFormat::Custom, None), parse_header(&header[..30]));
+    }
+}
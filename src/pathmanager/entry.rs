@@ -0,0 +1,90 @@
+use super::archive;
+use std::fs;
+use std::io::Result as IoResult;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    path: PathBuf,
+    size: u64,
+    modified: i64,
+    format: BackupFormat,
+    dump_timestamp: Option<i64>,
+}
+
+impl BackupEntry {
+    pub fn new(
+        path: &Path,
+        size: u64,
+        modified: i64,
+        format: BackupFormat,
+        dump_timestamp: Option<i64>,
+    ) -> BackupEntry {
+        BackupEntry {
+            path: path.into(),
+            size,
+            modified,
+            format,
+            dump_timestamp,
+        }
+    }
+
+    /// Reads file metadata and archive header. Header will be read only if `previous` entry
+    /// does not exists or file size or modification time were changed.
+    pub fn read(path: &Path, previous: Option<&BackupEntry>) -> IoResult<BackupEntry> {
+        let metadata = fs::metadata(path)?;
+        let size = metadata.len();
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+
+        if let Some(previous) = previous {
+            if previous.size == size && previous.modified == modified {
+                return Ok(previous.clone());
+            }
+        }
+
+        let (format, dump_timestamp) = archive::read_header(path)?;
+
+        Ok(BackupEntry::new(
+            path,
+            size,
+            modified,
+            format,
+            dump_timestamp,
+        ))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn modified(&self) -> i64 {
+        self.modified
+    }
+
+    pub fn format(&self) -> BackupFormat {
+        self.format
+    }
+
+    pub fn dump_timestamp(&self) -> Option<i64> {
+        self.dump_timestamp
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackupFormat {
+    Custom,
+    Tar,
+    Plain,
+    Gzip,
+    Unknown,
+}
@@ -1,7 +1,9 @@
+use serde_json::Error as JsonError;
 use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::io::Error as IoError;
 
 pub type PathManagerResult<T> = Result<T, PathManagerError>;
 
@@ -16,6 +18,24 @@ impl PathManagerError {
             message: message.into(),
         }
     }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn io_error(error: IoError) -> PathManagerError {
+        warn!("IO error - {}", error);
+
+        PathManagerError {
+            message: format!("{}", error),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn json_error(error: JsonError) -> PathManagerError {
+        warn!("JSON error - {}", error);
+
+        PathManagerError {
+            message: format!("{}", error),
+        }
+    }
 }
 
 impl Error for PathManagerError {}
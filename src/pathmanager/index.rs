@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Component;
+use std::path::Path;
+
+/// Minimal share of query word trigrams which must be found in path component.
+const FUZZY_THRESHOLD: f64 = 0.6;
+
+/// Weight multiplier for words found in file name instead of directory name.
+const FILE_NAME_BOOST: f64 = 2.0;
+
+type Trigram = (char, char, char);
+
+/// Trigram index over lower cased path components. Components are shared between paths, so
+/// lookup cost depends on number of distinct names rather than on number of paths.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    tokens: HashMap<String, usize>,
+    token_names: HashMap<usize, String>,
+    trigrams: HashMap<Trigram, Vec<usize>>,
+    postings: HashMap<usize, Vec<Posting>>,
+    next_token: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Posting {
+    id: usize,
+    file_name: bool,
+}
+
+impl SearchIndex {
+    pub fn new() -> SearchIndex {
+        SearchIndex::default()
+    }
+
+    pub fn insert(&mut self, id: usize, path: &Path) {
+        for (name, file_name) in path_tokens(path) {
+            let token = self.token_id(name);
+
+            self.postings
+                .entry(token)
+                .or_default()
+                .push(Posting { id, file_name });
+        }
+    }
+
+    pub fn remove(&mut self, id: usize, path: &Path) {
+        for (name, _) in path_tokens(path) {
+            let token = match self.tokens.get(&name) {
+                Some(&token) => token,
+                None => continue,
+            };
+            let is_empty = match self.postings.get_mut(&token) {
+                Some(postings) => {
+                    postings.retain(|posting| posting.id != id);
+                    postings.is_empty()
+                }
+                None => true,
+            };
+
+            if is_empty {
+                self.remove_token(token, &name);
+            }
+        }
+    }
+
+    /// Returns score for every path matching at least one query word. Every word contributes
+    /// its length multiplied by similarity with the best matching component.
+    pub fn search(&self, query: &str) -> HashMap<usize, f64> {
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for word in query
+            .to_lowercase()
+            .split(char::is_whitespace)
+            .filter(|w| !w.is_empty())
+        {
+            let weight = word.chars().count() as f64;
+            let mut word_scores: HashMap<usize, f64> = HashMap::new();
+
+            for (token, similarity) in self.match_tokens(word) {
+                for posting in self.postings.get(&token).into_iter().flatten() {
+                    let boost = if posting.file_name {
+                        FILE_NAME_BOOST
+                    } else {
+                        1.0
+                    };
+                    let score = word_scores.entry(posting.id).or_insert(0.0);
+
+                    *score = score.max(weight * similarity * boost);
+                }
+            }
+
+            for (id, word_score) in word_scores {
+                *scores.entry(id).or_insert(0.0) += word_score;
+            }
+        }
+
+        scores
+    }
+
+    fn match_tokens(&self, word: &str) -> Vec<(usize, f64)> {
+        let word_trigrams = trigrams(word);
+
+        // Words shorter than trigram can be matched only as substring.
+        if word_trigrams.is_empty() {
+            return self
+                .token_names
+                .iter()
+                .filter(|(_, name)| name.contains(word))
+                .map(|(&token, _)| (token, 1.0))
+                .collect();
+        }
+
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+
+        for trigram in &word_trigrams {
+            for &token in self.trigrams.get(trigram).into_iter().flatten() {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+        }
+
+        counts
+            .into_iter()
+            .filter_map(|(token, count)| {
+                let similarity = count as f64 / word_trigrams.len() as f64;
+
+                if similarity >= FUZZY_THRESHOLD {
+                    Some((token, similarity))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn token_id(&mut self, name: String) -> usize {
+        if let Some(&token) = self.tokens.get(&name) {
+            return token;
+        }
+
+        let token = self.next_token;
+
+        for trigram in trigrams(&name) {
+            self.trigrams.entry(trigram).or_default().push(token);
+        }
+
+        self.tokens.insert(name.clone(), token);
+        self.token_names.insert(token, name);
+        self.next_token += 1;
+
+        token
+    }
+
+    fn remove_token(&mut self, token: usize, name: &str) {
+        for trigram in trigrams(name) {
+            if let Some(tokens) = self.trigrams.get_mut(&trigram) {
+                tokens.retain(|&t| t != token);
+
+                if tokens.is_empty() {
+                    self.trigrams.remove(&trigram);
+                }
+            }
+        }
+
+        self.tokens.remove(name);
+        self.token_names.remove(&token);
+        self.postings.remove(&token);
+    }
+}
+
+fn path_tokens(path: &Path) -> Vec<(String, bool)> {
+    let names: Vec<_> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect();
+    let last = names.len().saturating_sub(1);
+
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| (name, index == last))
+        .collect()
+}
+
+fn trigrams(value: &str) -> HashSet<Trigram> {
+    let chars: Vec<_> = value.chars().collect();
+
+    chars.windows(3).map(|w| (w[0], w[1], w[2])).collect()
+}
@@ -1,18 +1,37 @@
+mod archive;
+mod entry;
 mod error;
+mod index;
+mod pattern;
 
+pub use self::entry::BackupEntry;
+pub use self::entry::BackupFormat;
 pub use self::error::PathManagerError;
 pub use self::error::PathManagerResult;
+pub use self::pattern::NamePatterns;
 
+use self::index::SearchIndex;
+use crate::config::ConfigRef;
+use glob::Pattern;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::RwLock;
 
 #[derive(Debug, Clone)]
 pub struct PathManagerRef {
     inner: Arc<RwLock<PathManager>>,
+    /// Serializes index saves from parallel directory workers, which share temporary file.
+    save_lock: Arc<Mutex<()>>,
 }
 
 impl PathManagerRef {
@@ -20,12 +39,12 @@ impl PathManagerRef {
     where
         F: FnOnce(&PathManager) -> PathManagerResult<T>,
     {
-        match self.inner.write() {
+        match self.inner.read() {
             Ok(ref pathmanager) => callback(pathmanager),
             Err(err) => {
-                warn!("Failed to acquire write lock - {}", err);
+                warn!("Failed to acquire read lock - {}", err);
 
-                Err(PathManagerError::new("Failed to acquire write lock"))
+                Err(PathManagerError::new("Failed to acquire read lock"))
             }
         }
     }
@@ -44,12 +63,54 @@ impl PathManagerRef {
         }
     }
 
-    pub fn query_paths<F>(&self, query: &str, n: usize, callback: F) -> PathManagerResult<()>
+    pub fn query_paths<F>(
+        &self,
+        query: &str,
+        order: SearchOrder,
+        offset: usize,
+        limit: usize,
+        callback: F,
+    ) -> PathManagerResult<usize>
     where
-        F: FnMut(&Path),
+        F: FnMut(&BackupEntry),
     {
         self.with_read(move |pathmanager| {
-            pathmanager.query_paths(query, n, callback);
+            Ok(pathmanager.query_paths(query, order, offset, limit, callback))
+        })
+    }
+
+    pub fn get_entry(&self, path: &Path) -> PathManagerResult<Option<BackupEntry>> {
+        self.with_read(move |pathmanager| Ok(pathmanager.get_entry(path).cloned()))
+    }
+
+    pub fn for_each_database<F>(&self, callback: F) -> PathManagerResult<()>
+    where
+        F: FnMut(&str, &[&BackupEntry]),
+    {
+        self.with_read(move |pathmanager| {
+            pathmanager.for_each_database(callback);
+
+            Ok(())
+        })
+    }
+
+    pub fn latest_backup(&self, database: &str) -> PathManagerResult<Option<BackupEntry>> {
+        self.with_read(move |pathmanager| Ok(pathmanager.latest_backup(database).cloned()))
+    }
+
+    pub fn latest_matching(&self, pattern: &Pattern) -> PathManagerResult<Option<BackupEntry>> {
+        self.with_read(move |pathmanager| Ok(pathmanager.latest_matching(pattern).cloned()))
+    }
+
+    /// Infers database name from backup path or URL using configured name patterns.
+    pub fn infer_name(&self, path: &str) -> PathManagerResult<Option<String>> {
+        self.with_read(move |pathmanager| Ok(pathmanager.patterns.infer_name(path)))
+    }
+
+    /// Replaces name patterns and groups known backups by databases inferred by new patterns.
+    pub fn set_patterns(&self, patterns: NamePatterns) -> PathManagerResult<()> {
+        self.with_write(move |pathmanager| {
+            pathmanager.set_patterns(patterns);
 
             Ok(())
         })
@@ -57,7 +118,7 @@ impl PathManagerRef {
 
     pub fn retain<F>(&self, callback: F) -> PathManagerResult<()>
     where
-        F: Fn(&PathBuf) -> bool,
+        F: Fn(&BackupEntry) -> bool,
     {
         self.with_write(move |pathmanager| {
             pathmanager.retain(callback);
@@ -66,129 +127,304 @@ impl PathManagerRef {
         })
     }
 
-    pub fn add_path(&self, path: &Path) -> PathManagerResult<()> {
+    pub fn add_entry(&self, entry: BackupEntry) -> PathManagerResult<()> {
         self.with_write(move |pathmanager| {
-            pathmanager.add_path(path);
+            pathmanager.add_entry(entry);
 
             Ok(())
         })
     }
+
+    pub fn save(&self) -> PathManagerResult<()> {
+        let _guard = match self.save_lock.lock() {
+            Ok(guard) => guard,
+            Err(err) => {
+                warn!("Failed to acquire save lock - {}", err);
+
+                return Err(PathManagerError::new("Failed to acquire save lock"));
+            }
+        };
+
+        self.with_read(move |pathmanager| pathmanager.save())
+    }
 }
 
 #[derive(Debug)]
 struct PathManager {
-    paths: HashMap<usize, PathBuf>,
-    index: HashMap<String, Vec<usize>>,
+    index_path: Option<PathBuf>,
+    paths: HashMap<usize, BackupEntry>,
+    ids: HashMap<PathBuf, usize>,
+    index: SearchIndex,
+    patterns: NamePatterns,
+    databases: BTreeMap<String, HashSet<usize>>,
     next_index: usize,
 }
 
 impl PathManager {
-    fn new() -> PathManager {
+    fn new(index_path: Option<PathBuf>, patterns: NamePatterns) -> PathManager {
         PathManager {
+            index_path,
             paths: HashMap::new(),
-            index: HashMap::new(),
+            ids: HashMap::new(),
+            index: SearchIndex::new(),
+            patterns,
+            databases: BTreeMap::new(),
             next_index: 0,
         }
     }
 
-    pub fn query_paths<F>(&self, query: &str, n: usize, mut callback: F)
+    /// Calls `callback` for `limit` matched entries starting from `offset` and returns total
+    /// number of matched entries.
+    pub fn query_paths<F>(
+        &self,
+        query: &str,
+        order: SearchOrder,
+        offset: usize,
+        limit: usize,
+        callback: F,
+    ) -> usize
     where
-        F: FnMut(&Path),
+        F: FnMut(&BackupEntry),
     {
-        let mut query_results: HashMap<usize, u64> = HashMap::new();
+        let mut results: Vec<_> = self
+            .index
+            .search(query)
+            .into_iter()
+            .filter_map(|(id, score)| self.paths.get(&id).map(|entry| (id, score, entry)))
+            .collect();
+        let total = results.len();
+
+        match order {
+            SearchOrder::Relevance => results.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(Ordering::Equal)
+                    .then(b.0.cmp(&a.0))
+            }),
+            SearchOrder::Recent => results.sort_by(|a, b| {
+                backup_time(b.2)
+                    .cmp(&backup_time(a.2))
+                    .then(b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal))
+            }),
+        }
+
+        results
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, _, entry)| entry)
+            .for_each(callback);
 
-        for word in query
-            .to_lowercase()
-            .split(char::is_whitespace)
-            .filter(|w| !w.is_empty())
-        {
-            let weight = word.len();
+        total
+    }
 
-            for (_, ids) in self.index.iter().filter(|(k, _)| k.contains(word)) {
-                for &id in ids {
-                    let w = query_results.entry(id).or_insert(0);
+    pub fn get_entry(&self, path: &Path) -> Option<&BackupEntry> {
+        self.ids.get(path).and_then(|id| self.paths.get(id))
+    }
 
-                    *w += weight as u64;
-                }
-            }
+    /// Calls `callback` for every database inferred by name patterns with its backups ordered
+    /// from newest to oldest.
+    pub fn for_each_database<F>(&self, mut callback: F)
+    where
+        F: FnMut(&str, &[&BackupEntry]),
+    {
+        for (database, ids) in &self.databases {
+            callback(database, &self.database_backups(ids));
         }
+    }
 
-        let mut results: Vec<_> = query_results.into_iter().map(|(id, w)| (w, id)).collect();
+    pub fn latest_backup(&self, database: &str) -> Option<&BackupEntry> {
+        self.databases
+            .get(database)
+            .and_then(|ids| self.database_backups(ids).first().copied())
+    }
 
-        results.sort_by(|a, b| b.cmp(a));
-        results
-            .into_iter()
-            .take(n)
-            .filter_map(|(_, id)| self.paths.get(&id))
-            .for_each(|p| callback(p));
+    /// Returns newest backup with path matching glob pattern.
+    pub fn latest_matching(&self, pattern: &Pattern) -> Option<&BackupEntry> {
+        self.paths
+            .values()
+            .filter(|entry| pattern.matches_path(entry.path()))
+            .max_by(|a, b| {
+                backup_time(a)
+                    .cmp(&backup_time(b))
+                    .then(a.path().cmp(b.path()))
+            })
+    }
+
+    fn database_backups(&self, ids: &HashSet<usize>) -> Vec<&BackupEntry> {
+        let mut backups: Vec<_> = ids.iter().filter_map(|id| self.paths.get(id)).collect();
+
+        backups.sort_by(|a, b| {
+            backup_time(b)
+                .cmp(&backup_time(a))
+                .then(b.path().cmp(a.path()))
+        });
+
+        backups
     }
 
     pub fn retain<F>(&mut self, callback: F)
     where
-        F: Fn(&PathBuf) -> bool,
+        F: Fn(&BackupEntry) -> bool,
     {
         let mut remove_ids = HashSet::new();
 
-        for (id, _) in self.paths.iter().filter(|(_, path)| !callback(path)) {
-            remove_ids.insert(id.clone());
+        for (id, _) in self.paths.iter().filter(|(_, entry)| !callback(entry)) {
+            remove_ids.insert(*id);
         }
 
-        for ids in self.index.values_mut() {
-            ids.retain(|id| !remove_ids.contains(id));
+        for id in &remove_ids {
+            if let Some(entry) = self.paths.remove(id) {
+                self.index.remove(*id, entry.path());
+                self.ids.remove(entry.path());
+                self.remove_database(*id, entry.path());
+            }
         }
-
-        self.paths.retain(|id, _| !remove_ids.contains(id));
-        self.index.retain(|_, ids| !ids.is_empty());
     }
 
-    pub fn add_path(&mut self, path: &Path) {
+    pub fn add_entry(&mut self, entry: BackupEntry) {
+        if let Some(id) = self.ids.get(entry.path()) {
+            self.paths.insert(*id, entry);
+
+            return;
+        }
+
         let index = self.next_index;
-        let path = path.to_path_buf();
 
-        for component in path.iter().skip(1) {
-            let name = component.to_string_lossy().to_lowercase();
+        self.index.insert(index, entry.path());
+        self.add_database(index, entry.path());
+        self.ids.insert(entry.path().into(), index);
+        self.paths.insert(index, entry);
+        self.next_index += 1;
+    }
+
+    pub fn set_patterns(&mut self, patterns: NamePatterns) {
+        let mut databases: BTreeMap<String, HashSet<usize>> = BTreeMap::new();
 
-            if let Some(ids) = self.index.get(&name) {
-                if ids
-                    .iter()
-                    .filter_map(|id| self.paths.get(id))
-                    .any(|p| p == &path)
-                {
-                    return;
+        for (id, entry) in &self.paths {
+            if let Some(database) = patterns.infer_name(&entry.path().to_string_lossy()) {
+                databases.entry(database).or_default().insert(*id);
+            }
+        }
+
+        self.patterns = patterns;
+        self.databases = databases;
+    }
+
+    fn add_database(&mut self, id: usize, path: &Path) {
+        if let Some(database) = self.patterns.infer_name(&path.to_string_lossy()) {
+            self.databases.entry(database).or_default().insert(id);
+        }
+    }
+
+    fn remove_database(&mut self, id: usize, path: &Path) {
+        if let Some(database) = self.patterns.infer_name(&path.to_string_lossy()) {
+            let is_empty = match self.databases.get_mut(&database) {
+                Some(ids) => {
+                    ids.remove(&id);
+                    ids.is_empty()
                 }
+                None => false,
+            };
+
+            if is_empty {
+                self.databases.remove(&database);
             }
+        }
+    }
+
+    fn save(&self) -> PathManagerResult<()> {
+        let index_path = match self.index_path {
+            Some(ref index_path) => index_path,
+            None => return Ok(()),
+        };
+        let temp_path = index_path.with_extension("tmp");
+        let entries: Vec<_> = self.paths.values().collect();
+        let writer = File::create(&temp_path).map_err(PathManagerError::io_error)?;
+
+        serde_json::to_writer(BufWriter::new(writer), &entries)
+            .map_err(PathManagerError::json_error)?;
+        fs::rename(&temp_path, index_path).map_err(PathManagerError::io_error)?;
+
+        debug!("Saved {} paths to {}", entries.len(), index_path.display());
 
-            self.index
-                .entry(name)
-                .or_insert_with(|| Vec::with_capacity(4))
-                .push(index);
+        Ok(())
+    }
+
+    fn load(&mut self) -> PathManagerResult<()> {
+        let index_path = match self.index_path {
+            Some(ref index_path) if index_path.is_file() => index_path,
+            _ => return Ok(()),
+        };
+        let reader = File::open(index_path).map_err(PathManagerError::io_error)?;
+        let entries: Vec<BackupEntry> = serde_json::from_reader(BufReader::new(reader))
+            .map_err(PathManagerError::json_error)?;
+
+        info!(
+            "Loaded {} paths from {}",
+            entries.len(),
+            index_path.display()
+        );
+
+        for entry in entries {
+            self.add_entry(entry);
         }
 
-        self.paths.insert(index, path);
-        self.next_index += 1;
+        Ok(())
     }
 }
 
-pub fn create() -> PathManagerRef {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum SearchOrder {
+    #[default]
+    Relevance,
+    Recent,
+}
+
+fn backup_time(entry: &BackupEntry) -> i64 {
+    entry.dump_timestamp().unwrap_or_else(|| entry.modified())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn create(config: ConfigRef) -> PathManagerRef {
+    let index_path = config.search_config().index_path().map(PathBuf::from);
+    let patterns = NamePatterns::new(config.name_patterns());
+    let mut path_manager = PathManager::new(index_path, patterns);
+
+    if let Err(err) = path_manager.load() {
+        warn!("Failed to load paths index - {}", err);
+    }
+
     PathManagerRef {
-        inner: Arc::new(RwLock::new(PathManager::new())),
+        inner: Arc::new(RwLock::new(path_manager)),
+        save_lock: Arc::new(Mutex::new(())),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::BackupEntry;
+    use super::BackupFormat;
+    use super::NamePatterns;
     use super::PathManager;
+    use super::SearchOrder;
 
+    use std::path::Path;
     use std::path::PathBuf;
 
+    fn entry(path: &Path) -> BackupEntry {
+        BackupEntry::new(path, 0, 0, BackupFormat::Custom, None)
+    }
+
     #[test]
     fn query_shold_return_matched() {
         let path: PathBuf = "/test/dir/file.backup".into();
-        let mut manager = PathManager::new();
+        let mut manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.add_path(&path);
-        manager.query_paths("dir", 2, |p| result.push(p.to_path_buf()));
+        manager.add_entry(entry(&path));
+        manager.query_paths("dir", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(vec![path], result);
     }
@@ -197,12 +433,14 @@ mod tests {
     fn query_shold_return_only_matched() {
         let path_1: PathBuf = "/test/dir/file.backup".into();
         let path_2: PathBuf = "/test/file.backup".into();
-        let mut manager = PathManager::new();
+        let mut manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.add_path(&path_1);
-        manager.add_path(&path_2);
-        manager.query_paths("dir", 2, |p| result.push(p.to_path_buf()));
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_2));
+        manager.query_paths("dir", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(vec![path_1], result);
     }
@@ -211,22 +449,26 @@ mod tests {
     fn query_shold_return_relevant_paths() {
         let path_1: PathBuf = "/test/dir/file.backup".into();
         let path_2: PathBuf = "/test/other/file.backup".into();
-        let mut manager = PathManager::new();
+        let mut manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.add_path(&path_1);
-        manager.add_path(&path_2);
-        manager.query_paths("file other", 2, |p| result.push(p.to_path_buf()));
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_2));
+        manager.query_paths("file other", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(vec![path_2, path_1], result);
     }
 
     #[test]
     fn query_shold_return_empty() {
-        let manager = PathManager::new();
+        let manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.query_paths("file other", 2, |p| result.push(p.to_path_buf()));
+        manager.query_paths("file other", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(Vec::<PathBuf>::new(), result);
     }
@@ -236,13 +478,15 @@ mod tests {
         let path_1: PathBuf = "/test/dir/file.backup".into();
         let path_2: PathBuf = "/test/other/file.backup".into();
         let path_3: PathBuf = "/test/other.backup".into();
-        let mut manager = PathManager::new();
+        let mut manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.add_path(&path_1);
-        manager.add_path(&path_2);
-        manager.add_path(&path_3);
-        manager.query_paths("file other", 2, |p| result.push(p.to_path_buf()));
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_2));
+        manager.add_entry(entry(&path_3));
+        manager.query_paths("file other", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(vec![path_2, path_3], result);
     }
@@ -252,14 +496,16 @@ mod tests {
         let path_1: PathBuf = "/test/dir/file.backup".into();
         let path_2: PathBuf = "/test/other/file.backup".into();
         let path_3: PathBuf = "/test/other.backup".into();
-        let mut manager = PathManager::new();
+        let mut manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.add_path(&path_1);
-        manager.add_path(&path_2);
-        manager.add_path(&path_2);
-        manager.add_path(&path_3);
-        manager.query_paths("file other", 2, |p| result.push(p.to_path_buf()));
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_2));
+        manager.add_entry(entry(&path_2));
+        manager.add_entry(entry(&path_3));
+        manager.query_paths("file other", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(vec![path_2, path_3], result);
     }
@@ -269,15 +515,243 @@ mod tests {
         let path_1: PathBuf = "/test/dir/file.backup".into();
         let path_2: PathBuf = "/test/other/file.backup".into();
         let path_3: PathBuf = "/test/different/file.backup".into();
-        let mut manager = PathManager::new();
+        let mut manager = PathManager::new(None, NamePatterns::default());
         let mut result = Vec::new();
 
-        manager.add_path(&path_1);
-        manager.add_path(&path_2);
-        manager.add_path(&path_3);
-        manager.retain(|path| path != &path_2);
-        manager.query_paths("diff file", 3, |p| result.push(p.to_path_buf()));
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_2));
+        manager.add_entry(entry(&path_3));
+        manager.retain(|entry| entry.path() != path_2);
+        manager.query_paths("diff file", SearchOrder::Relevance, 0, 3, |e| {
+            result.push(e.path().to_path_buf())
+        });
 
         assert_eq!(vec![path_3, path_1], result);
     }
+
+    #[test]
+    fn add_shold_update_existing_entry() {
+        let path: PathBuf = "/test/dir/file.backup".into();
+        let updated = BackupEntry::new(&path, 10, 20, BackupFormat::Tar, Some(30));
+        let mut manager = PathManager::new(None, NamePatterns::default());
+        let mut result = Vec::new();
+
+        manager.add_entry(entry(&path));
+        manager.add_entry(updated.clone());
+        manager.query_paths("file", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.clone())
+        });
+
+        assert_eq!(vec![updated], result);
+    }
+
+    #[test]
+    fn load_shold_restore_saved_entries() {
+        let index_path =
+            std::env::temp_dir().join(format!("pgrestore-index-{}.json", std::process::id()));
+        let path: PathBuf = "/test/dir/file.backup".into();
+        let saved = BackupEntry::new(&path, 10, 20, BackupFormat::Custom, Some(30));
+        let mut manager = PathManager::new(Some(index_path.clone()), NamePatterns::default());
+
+        manager.add_entry(saved.clone());
+        manager.save().unwrap();
+
+        let mut manager = PathManager::new(Some(index_path.clone()), NamePatterns::default());
+
+        manager.load().unwrap();
+        std::fs::remove_file(&index_path).unwrap();
+
+        assert_eq!(Some(&saved), manager.get_entry(&path));
+    }
+
+    #[test]
+    fn query_shold_match_misspelled_words() {
+        let path_1: PathBuf = "/backups/production/db.backup".into();
+        let path_2: PathBuf = "/backups/staging/db.backup".into();
+        let mut manager = PathManager::new(None, NamePatterns::default());
+        let mut result = Vec::new();
+
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_2));
+        manager.query_paths("prodction", SearchOrder::Relevance, 0, 2, |e| {
+            result.push(e.path().to_path_buf())
+        });
+
+        assert_eq!(vec![path_1], result);
+    }
+
+    #[test]
+    fn query_shold_prefer_file_name_matches() {
+        let path_1: PathBuf = "/test/sales/db.backup".into();
+        let path_2: PathBuf = "/test/other/sales.backup".into();
+        let path_3: PathBuf = "/test/sales/other.backup".into();
+        let mut manager = PathManager::new(None, NamePatterns::default());
+        let mut result = Vec::new();
+
+        manager.add_entry(entry(&path_2));
+        manager.add_entry(entry(&path_1));
+        manager.add_entry(entry(&path_3));
+        manager.query_paths("sales", SearchOrder::Relevance, 0, 1, |e| {
+            result.push(e.path().to_path_buf())
+        });
+
+        assert_eq!(vec![path_2], result);
+    }
+
+    #[test]
+    fn query_shold_order_by_backup_time() {
+        let path_1: PathBuf = "/test/first.backup".into();
+        let path_2: PathBuf = "/test/second.backup".into();
+        let path_3: PathBuf = "/test/third.backup".into();
+        let mut manager = PathManager::new(None, NamePatterns::default());
+        let mut result = Vec::new();
+
+        manager.add_entry(BackupEntry::new(
+            &path_1,
+            0,
+            300,
+            BackupFormat::Custom,
+            None,
+        ));
+        manager.add_entry(BackupEntry::new(
+            &path_2,
+            0,
+            900,
+            BackupFormat::Custom,
+            Some(200),
+        ));
+        manager.add_entry(BackupEntry::new(
+            &path_3,
+            0,
+            100,
+            BackupFormat::Custom,
+            Some(400),
+        ));
+        manager.query_paths("test", SearchOrder::Recent, 0, 3, |e| {
+            result.push(e.path().to_path_buf())
+        });
+
+        assert_eq!(vec![path_3, path_1, path_2], result);
+    }
+
+    #[test]
+    fn query_shold_return_requested_page() {
+        let mut manager = PathManager::new(None, NamePatterns::default());
+        let mut result = Vec::new();
+
+        for index in 0..10 {
+            manager.add_entry(BackupEntry::new(
+                &PathBuf::from(format!("/test/file-{}.backup", index)),
+                0,
+                index,
+                BackupFormat::Custom,
+                None,
+            ));
+        }
+
+        let total = manager.query_paths("file", SearchOrder::Recent, 4, 3, |e| {
+            result.push(e.path().to_path_buf())
+        });
+
+        assert_eq!(10, total);
+        assert_eq!(
+            vec![
+                PathBuf::from("/test/file-5.backup"),
+                PathBuf::from("/test/file-4.backup"),
+                PathBuf::from("/test/file-3.backup"),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn databases_shold_group_backups_by_name() {
+        let config = serde_yaml::from_str::<Vec<crate::config::NamePattern>>(
+            r#"[{path_pattern: '/(\w+)_\d+\.backup$', replace_pattern: '$1'}]"#,
+        )
+        .unwrap();
+        let path_1: PathBuf = "/test/sales_1.backup".into();
+        let path_2: PathBuf = "/test/sales_2.backup".into();
+        let path_3: PathBuf = "/test/stock_1.backup".into();
+        let path_4: PathBuf = "/test/unknown.backup".into();
+        let mut manager = PathManager::new(None, NamePatterns::new(&config));
+        let mut result = Vec::new();
+
+        manager.add_entry(BackupEntry::new(
+            &path_1,
+            0,
+            100,
+            BackupFormat::Custom,
+            None,
+        ));
+        manager.add_entry(BackupEntry::new(
+            &path_2,
+            0,
+            200,
+            BackupFormat::Custom,
+            None,
+        ));
+        manager.add_entry(BackupEntry::new(
+            &path_3,
+            0,
+            300,
+            BackupFormat::Custom,
+            None,
+        ));
+        manager.add_entry(entry(&path_4));
+        manager.for_each_database(|database, backups| {
+            result.push((
+                database.to_string(),
+                backups
+                    .iter()
+                    .map(|e| e.path().to_path_buf())
+                    .collect::<Vec<_>>(),
+            ))
+        });
+
+        assert_eq!(
+            vec![
+                ("sales".to_string(), vec![path_2.clone(), path_1.clone()]),
+                ("stock".to_string(), vec![path_3.clone()]),
+            ],
+            result
+        );
+        assert_eq!(
+            Some(path_2.as_path()),
+            manager.latest_backup("sales").map(|e| e.path())
+        );
+
+        manager.retain(|entry| entry.path() != path_2 && entry.path() != path_3);
+
+        assert_eq!(
+            Some(path_1.as_path()),
+            manager.latest_backup("sales").map(|e| e.path())
+        );
+        assert_eq!(None, manager.latest_backup("stock"));
+    }
+
+    #[test]
+    fn set_patterns_shold_regroup_backups() {
+        let config = serde_yaml::from_str::<Vec<crate::config::NamePattern>>(
+            r#"[{path_pattern: '/(\w+)_\d+\.backup$', replace_pattern: '$1'}]"#,
+        )
+        .unwrap();
+        let path: PathBuf = "/test/sales_1.backup".into();
+        let mut manager = PathManager::new(None, NamePatterns::default());
+
+        manager.add_entry(entry(&path));
+
+        assert!(manager.latest_backup("sales").is_none());
+
+        manager.set_patterns(NamePatterns::new(&config));
+
+        assert_eq!(
+            Some(path.as_path()),
+            manager.latest_backup("sales").map(|e| e.path())
+        );
+        assert_eq!(
+            Some("sales".to_string()),
+            manager.patterns.infer_name("/a/sales_2.backup")
+        );
+    }
 }
@@ -0,0 +1,165 @@
+use crate::config::ChangeCase;
+use crate::config::NamePattern as NamePatternConfig;
+use regex::Captures;
+use regex::Regex;
+
+/// Server side counterpart of UI name patterns. Infers logical database name from backup path
+/// or URL using first matched pattern.
+#[derive(Debug, Clone, Default)]
+pub struct NamePatterns {
+    patterns: Vec<NamePattern>,
+}
+
+#[derive(Debug, Clone)]
+struct NamePattern {
+    regex: Regex,
+    replace_pattern: String,
+    change_case: ChangeCase,
+}
+
+impl NamePatterns {
+    pub fn new(config: &[NamePatternConfig]) -> NamePatterns {
+        let patterns = config
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern.path_pattern()) {
+                Ok(regex) => Some(NamePattern {
+                    regex,
+                    replace_pattern: pattern.replace_pattern().into(),
+                    change_case: pattern.change_case(),
+                }),
+                Err(err) => {
+                    warn!("Name pattern {} skipped - {}", pattern.path_pattern(), err);
+
+                    None
+                }
+            })
+            .collect();
+
+        NamePatterns { patterns }
+    }
+
+    pub fn infer_name(&self, path: &str) -> Option<String> {
+        self.patterns.iter().find_map(|pattern| {
+            pattern
+                .regex
+                .captures(path)
+                .map(|captures| expand(&pattern.replace_pattern, &captures, pattern.change_case))
+        })
+    }
+}
+
+/// Replaces `$N` groups in template, unmatched groups replaced by empty string. Case of groups
+/// changed, but template text kept as is.
+fn expand(template: &str, captures: &Captures, change_case: ChangeCase) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' || !chars.peek().is_some_and(char::is_ascii_digit) {
+            result.push(c);
+
+            continue;
+        }
+
+        let mut index = 0;
+
+        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+            index = index * 10 + digit as usize;
+            chars.next();
+        }
+
+        if let Some(group) = captures.get(index) {
+            match change_case {
+                ChangeCase::NoChange => result.push_str(group.as_str()),
+                ChangeCase::Upper => result.push_str(&group.as_str().to_uppercase()),
+                ChangeCase::Lower => result.push_str(&group.as_str().to_lowercase()),
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChangeCase;
+    use super::NamePattern;
+    use super::NamePatterns;
+    use regex::Regex;
+
+    fn patterns(patterns: &[(&str, &str)]) -> NamePatterns {
+        NamePatterns {
+            patterns: patterns
+                .iter()
+                .map(|(regex, replace_pattern)| NamePattern {
+                    regex: Regex::new(regex).unwrap(),
+                    replace_pattern: replace_pattern.to_string(),
+                    change_case: ChangeCase::NoChange,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn infer_name_shold_replace_groups() {
+        let patterns = patterns(&[(r"/(\w+)/(\w+)_\d+\.backup$", "$1_$2")]);
+
+        assert_eq!(
+            Some("prod_sales".to_string()),
+            patterns.infer_name("/backups/prod/sales_20240101.backup")
+        );
+    }
+
+    #[test]
+    fn infer_name_shold_use_first_matched_pattern() {
+        let patterns = patterns(&[
+            (r"/archive/(\w+)\.backup$", "old_$1"),
+            (r"/(\w+)\.backup$", "$1"),
+        ]);
+
+        assert_eq!(
+            Some("sales".to_string()),
+            patterns.infer_name("/backups/sales.backup")
+        );
+        assert_eq!(
+            Some("old_sales".to_string()),
+            patterns.infer_name("/archive/sales.backup")
+        );
+    }
+
+    #[test]
+    fn infer_name_shold_skip_missing_groups() {
+        let patterns = patterns(&[(r"/(\w+)\.backup$", "$1$2$")]);
+
+        assert_eq!(
+            Some("sales$".to_string()),
+            patterns.infer_name("/backups/sales.backup")
+        );
+    }
+
+    #[test]
+    fn infer_name_shold_return_none() {
+        let patterns = patterns(&[(r"/(\w+)\.backup$", "$1")]);
+
+        assert_eq!(None, patterns.infer_name("/backups/sales.dump"));
+    }
+
+    #[test]
+    fn infer_name_shold_change_case_of_groups() {
+        let mut patterns = patterns(&[(r"/(\w+)\.backup$", "Db_$1")]);
+
+        patterns.patterns[0].change_case = ChangeCase::Lower;
+
+        assert_eq!(
+            Some("Db_sales".to_string()),
+            patterns.infer_name("/backups/SALES.backup")
+        );
+
+        patterns.patterns[0].change_case = ChangeCase::Upper;
+
+        assert_eq!(
+            Some("Db_SALES".to_string()),
+            patterns.infer_name("/backups/Sales.backup")
+        );
+    }
+}
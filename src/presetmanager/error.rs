@@ -0,0 +1,47 @@
+use serde_json::Error as JsonError;
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::io::Error as IoError;
+
+pub type PresetManagerResult<T> = Result<T, PresetManagerError>;
+
+#[derive(Debug)]
+pub struct PresetManagerError {
+    message: String,
+}
+
+impl PresetManagerError {
+    pub fn new(message: &str) -> PresetManagerError {
+        PresetManagerError {
+            message: message.into(),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn io_error(error: IoError) -> PresetManagerError {
+        warn!("IO error - {}", error);
+
+        PresetManagerError {
+            message: format!("{}", error),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn json_error(error: JsonError) -> PresetManagerError {
+        warn!("JSON error - {}", error);
+
+        PresetManagerError {
+            message: format!("{}", error),
+        }
+    }
+}
+
+impl Error for PresetManagerError {}
+
+impl Display for PresetManagerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
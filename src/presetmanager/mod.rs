@@ -0,0 +1,329 @@
+mod error;
+
+pub use self::error::PresetManagerError;
+pub use self::error::PresetManagerResult;
+
+use crate::config::ConfigRef;
+use crate::launcher::RestoreRequest;
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone)]
+pub struct PresetManagerRef {
+    inner: Arc<RwLock<PresetManager>>,
+}
+
+impl PresetManagerRef {
+    fn with_read<F, T>(&self, callback: F) -> PresetManagerResult<T>
+    where
+        F: FnOnce(&PresetManager) -> PresetManagerResult<T>,
+    {
+        match self.inner.read() {
+            Ok(ref presetmanager) => callback(presetmanager),
+            Err(err) => {
+                warn!("Failed to acquire read lock - {}", err);
+
+                Err(PresetManagerError::new("Failed to acquire read lock"))
+            }
+        }
+    }
+
+    fn with_write<F, T>(&self, callback: F) -> PresetManagerResult<T>
+    where
+        F: FnOnce(&mut PresetManager) -> PresetManagerResult<T>,
+    {
+        match self.inner.write() {
+            Ok(ref mut presetmanager) => callback(presetmanager),
+            Err(err) => {
+                warn!("Failed to acquire write lock - {}", err);
+
+                Err(PresetManagerError::new("Failed to acquire write lock"))
+            }
+        }
+    }
+
+    pub fn for_each<F>(&self, callback: F) -> PresetManagerResult<()>
+    where
+        F: FnMut(&Preset),
+    {
+        self.with_read(move |presetmanager| {
+            presetmanager.presets.values().for_each(callback);
+
+            Ok(())
+        })
+    }
+
+    pub fn get(&self, id: usize) -> PresetManagerResult<Preset> {
+        self.with_read(move |presetmanager| {
+            presetmanager
+                .presets
+                .get(&id)
+                .cloned()
+                .ok_or_else(|| PresetManagerError::new("Preset not found"))
+        })
+    }
+
+    pub fn create(&self, preset: PresetData) -> PresetManagerResult<usize> {
+        self.with_write(move |presetmanager| presetmanager.create(preset))
+    }
+
+    pub fn update(&self, id: usize, preset: PresetData) -> PresetManagerResult<()> {
+        self.with_write(move |presetmanager| presetmanager.update(id, preset))
+    }
+
+    pub fn delete(&self, id: usize) -> PresetManagerResult<()> {
+        self.with_write(move |presetmanager| presetmanager.delete(id))
+    }
+}
+
+#[derive(Debug)]
+struct PresetManager {
+    presets_path: Option<PathBuf>,
+    presets: BTreeMap<usize, Preset>,
+    next_id: usize,
+}
+
+impl PresetManager {
+    fn new(presets_path: Option<PathBuf>) -> PresetManager {
+        PresetManager {
+            presets_path,
+            presets: BTreeMap::new(),
+            next_id: 1,
+        }
+    }
+
+    fn create(&mut self, preset: PresetData) -> PresetManagerResult<usize> {
+        self.check_name(None, &preset.name)?;
+
+        let id = self.next_id;
+        let mut presets = self.presets.clone();
+
+        presets.insert(id, Preset { id, data: preset });
+        self.replace(presets)?;
+        self.next_id += 1;
+
+        Ok(id)
+    }
+
+    fn update(&mut self, id: usize, preset: PresetData) -> PresetManagerResult<()> {
+        self.check_name(Some(id), &preset.name)?;
+
+        let mut presets = self.presets.clone();
+
+        match presets.get_mut(&id) {
+            Some(existing) => existing.data = preset,
+            None => return Err(PresetManagerError::new("Preset not found")),
+        }
+
+        self.replace(presets)
+    }
+
+    fn delete(&mut self, id: usize) -> PresetManagerResult<()> {
+        let mut presets = self.presets.clone();
+
+        if presets.remove(&id).is_none() {
+            return Err(PresetManagerError::new("Preset not found"));
+        }
+
+        self.replace(presets)
+    }
+
+    /// Saves presets and replaces current presets only if saved, failed change is not kept in
+    /// memory.
+    fn replace(&mut self, presets: BTreeMap<usize, Preset>) -> PresetManagerResult<()> {
+        save(self.presets_path.as_ref(), &presets)?;
+        self.presets = presets;
+
+        Ok(())
+    }
+
+    fn check_name(&self, id: Option<usize>, name: &str) -> PresetManagerResult<()> {
+        if name.is_empty() {
+            return Err(PresetManagerError::new("Preset name must not be empty"));
+        }
+
+        if self
+            .presets
+            .values()
+            .any(|preset| Some(preset.id) != id && preset.data.name == name)
+        {
+            return Err(PresetManagerError::new("Preset name already exists"));
+        }
+
+        Ok(())
+    }
+
+    fn load(&mut self) -> PresetManagerResult<()> {
+        let presets_path = match self.presets_path {
+            Some(ref presets_path) if presets_path.is_file() => presets_path,
+            _ => return Ok(()),
+        };
+        let reader = File::open(presets_path).map_err(PresetManagerError::io_error)?;
+        let presets: Vec<Preset> = serde_json::from_reader(BufReader::new(reader))
+            .map_err(PresetManagerError::json_error)?;
+
+        info!(
+            "Loaded {} presets from {}",
+            presets.len(),
+            presets_path.display()
+        );
+
+        for preset in presets {
+            self.next_id = self.next_id.max(preset.id + 1);
+            self.presets.insert(preset.id, preset);
+        }
+
+        Ok(())
+    }
+}
+
+fn save(
+    presets_path: Option<&PathBuf>,
+    presets: &BTreeMap<usize, Preset>,
+) -> PresetManagerResult<()> {
+    let presets_path = match presets_path {
+        Some(presets_path) => presets_path,
+        None => return Ok(()),
+    };
+    let temp_path = presets_path.with_extension("tmp");
+    let presets: Vec<_> = presets.values().collect();
+    let writer = File::create(&temp_path).map_err(PresetManagerError::io_error)?;
+
+    serde_json::to_writer_pretty(BufWriter::new(writer), &presets)
+        .map_err(PresetManagerError::json_error)?;
+    fs::rename(&temp_path, presets_path).map_err(PresetManagerError::io_error)?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    id: usize,
+    #[serde(flatten)]
+    data: PresetData,
+}
+
+impl Preset {
+    pub fn request(&self) -> &RestoreRequest {
+        &self.data.request
+    }
+}
+
+/// User editable part of preset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetData {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    request: RestoreRequest,
+}
+
+impl PresetData {
+    pub fn request(&self) -> &RestoreRequest {
+        &self.request
+    }
+
+    pub fn with_request(mut self, request: RestoreRequest) -> PresetData {
+        self.request = request;
+
+        self
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn create(config: ConfigRef) -> PresetManagerRef {
+    let presets_path = config.presets_path().map(PathBuf::from);
+    let mut preset_manager = PresetManager::new(presets_path);
+
+    if let Err(err) = preset_manager.load() {
+        warn!("Failed to load presets - {}", err);
+    }
+
+    PresetManagerRef {
+        inner: Arc::new(RwLock::new(preset_manager)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PresetData;
+    use super::PresetManager;
+
+    fn preset(name: &str) -> PresetData {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "request": {
+                "destination": 0,
+                "backup": {"type": "Path", "path": "/backups/sales.backup"},
+                "database": "Exists",
+                "restore": {"type": "Full"},
+                "ignore_errors": false,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn create_shold_reject_duplicate_names() {
+        let mut manager = PresetManager::new(None);
+
+        assert_eq!(1, manager.create(preset("sales")).unwrap());
+        assert_eq!(2, manager.create(preset("stock")).unwrap());
+        assert!(manager.create(preset("sales")).is_err());
+        assert!(manager.update(2, preset("sales")).is_err());
+        assert!(manager.update(1, preset("sales")).is_ok());
+    }
+
+    #[test]
+    fn delete_shold_remove_preset() {
+        let mut manager = PresetManager::new(None);
+        let id = manager.create(preset("sales")).unwrap();
+
+        manager.delete(id).unwrap();
+
+        assert!(manager.presets.is_empty());
+        assert!(manager.delete(id).is_err());
+    }
+
+    #[test]
+    fn load_shold_restore_saved_presets() {
+        let presets_path =
+            std::env::temp_dir().join(format!("pgrestore-presets-{}.json", std::process::id()));
+        let mut manager = PresetManager::new(Some(presets_path.clone()));
+
+        manager.create(preset("sales")).unwrap();
+        manager.create(preset("stock")).unwrap();
+        manager.delete(1).unwrap();
+
+        let mut manager = PresetManager::new(Some(presets_path.clone()));
+
+        manager.load().unwrap();
+        std::fs::remove_file(&presets_path).unwrap();
+
+        assert_eq!(vec![&2], manager.presets.keys().collect::<Vec<_>>());
+        assert_eq!("stock", manager.presets[&2].data.name);
+        assert_eq!(3, manager.create(preset("sales")).unwrap());
+    }
+
+    #[test]
+    fn failed_save_shold_keep_presets() {
+        let presets_path = std::env::temp_dir().join("pgrestore-missing/presets.json");
+        let mut manager = PresetManager::new(None);
+
+        manager.create(preset("sales")).unwrap();
+        manager.presets_path = Some(presets_path);
+
+        assert!(manager.create(preset("stock")).is_err());
+        assert!(manager.update(1, preset("orders")).is_err());
+        assert!(manager.delete(1).is_err());
+        assert_eq!(vec![&1], manager.presets.keys().collect::<Vec<_>>());
+        assert_eq!("sales", manager.presets[&1].data.name);
+        assert_eq!(2, manager.next_id);
+    }
+}
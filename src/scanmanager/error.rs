@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+pub type ScanManagerResult<T> = Result<T, ScanManagerError>;
+
+#[derive(Debug)]
+pub struct ScanManagerError {
+    message: String,
+}
+
+impl ScanManagerError {
+    pub fn new(message: &str) -> ScanManagerError {
+        ScanManagerError {
+            message: message.into(),
+        }
+    }
+}
+
+impl Error for ScanManagerError {}
+
+impl Display for ScanManagerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,362 @@
+mod error;
+
+pub use self::error::ScanManagerError;
+pub use self::error::ScanManagerResult;
+
+use crate::config::Config;
+use crate::config::ConfigRef;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
+use time::OffsetDateTime;
+
+const MAX_ERRORS: usize = 20;
+const SLOT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct ScanManagerRef {
+    inner: Arc<(Mutex<ScanManager>, Condvar)>,
+}
+
+impl ScanManagerRef {
+    fn lock(&self) -> ScanManagerResult<MutexGuard<'_, ScanManager>> {
+        match self.inner.0.lock() {
+            Ok(scanmanager) => Ok(scanmanager),
+            Err(err) => {
+                warn!("Failed to acquire lock - {}", err);
+
+                Err(ScanManagerError::new("Failed to acquire lock"))
+            }
+        }
+    }
+
+    /// Requests rescan of given directory or all directories if `directory` is `None`.
+    pub fn request_rescan(&self, directory: Option<&Path>) -> ScanManagerResult<()> {
+        self.lock()?.request_rescan(directory)?;
+        self.inner.1.notify_all();
+
+        Ok(())
+    }
+
+    /// Waits for rescan request of `directory` at most `timeout`, returns immediately if
+    /// directory is removed from configuration.
+    pub fn wait_rescan(&self, directory: &Path, timeout: Duration) -> ScanManagerResult<()> {
+        let deadline = Instant::now() + timeout;
+        let mut scanmanager = self.lock()?;
+
+        while scanmanager.statuses.contains_key(directory) && !scanmanager.pending.remove(directory)
+        {
+            let now = Instant::now();
+
+            if now >= deadline {
+                break;
+            }
+
+            scanmanager = self.wait(scanmanager, deadline - now)?;
+        }
+
+        Ok(())
+    }
+
+    /// Marks scan of `directory` as started, waits for free slot if number of parallel scans
+    /// is limited.
+    pub fn scan_started(&self, directory: &Path) -> ScanManagerResult<()> {
+        let mut scanmanager = self.lock()?;
+
+        while !scanmanager.has_free_slot() {
+            scanmanager = self.wait(scanmanager, SLOT_WAIT_TIMEOUT)?;
+        }
+
+        scanmanager.scan_started(directory);
+
+        Ok(())
+    }
+
+    pub fn scan_finished(
+        &self,
+        directory: &Path,
+        files_found: usize,
+        errors: &[String],
+    ) -> ScanManagerResult<()> {
+        self.lock()?.scan_finished(directory, files_found, errors);
+        self.inner.1.notify_all();
+
+        Ok(())
+    }
+
+    /// Replaces configured directories, statuses of kept directories are preserved. Returns
+    /// added directories which have no running scan worker.
+    pub fn configure(&self, config: &Config) -> ScanManagerResult<Vec<PathBuf>> {
+        let directories = config
+            .search_config()
+            .directories()
+            .iter()
+            .map(|directory| directory.path().into())
+            .collect();
+        let added = self
+            .lock()?
+            .configure(directories, config.search_config().max_parallel_scans());
+
+        self.inner.1.notify_all();
+
+        Ok(added)
+    }
+
+    /// Marks scan worker of `directory` as stopped if directory is removed from configuration,
+    /// returns `true` if worker should stop.
+    pub fn stop_removed(&self, directory: &Path) -> ScanManagerResult<bool> {
+        let mut scanmanager = self.lock()?;
+
+        if scanmanager.statuses.contains_key(directory) {
+            return Ok(false);
+        }
+
+        scanmanager.workers.remove(directory);
+
+        Ok(true)
+    }
+
+    /// Checks if `directory` is in current configuration.
+    pub fn is_configured(&self, directory: &Path) -> ScanManagerResult<bool> {
+        Ok(self.lock()?.statuses.contains_key(directory))
+    }
+
+    fn wait<'a>(
+        &self,
+        scanmanager: MutexGuard<'a, ScanManager>,
+        timeout: Duration,
+    ) -> ScanManagerResult<MutexGuard<'a, ScanManager>> {
+        match self.inner.1.wait_timeout(scanmanager, timeout) {
+            Ok((scanmanager, _)) => Ok(scanmanager),
+            Err(err) => {
+                warn!("Failed to wait scan manager - {}", err);
+
+                Err(ScanManagerError::new("Failed to wait scan manager"))
+            }
+        }
+    }
+
+    pub fn for_each<F>(&self, callback: F) -> ScanManagerResult<()>
+    where
+        F: FnMut(&Path, &ScanStatus),
+    {
+        self.lock()?.for_each(callback);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ScanManager {
+    pending: HashSet<PathBuf>,
+    statuses: BTreeMap<PathBuf, ScanStatus>,
+    workers: HashSet<PathBuf>,
+    max_running: Option<usize>,
+}
+
+impl ScanManager {
+    #[allow(clippy::needless_pass_by_value)]
+    fn new(config: ConfigRef) -> ScanManager {
+        let statuses: BTreeMap<_, _> = config
+            .search_config()
+            .directories()
+            .iter()
+            .map(|directory| (directory.path().into(), ScanStatus::default()))
+            .collect();
+        let workers = statuses.keys().cloned().collect();
+
+        ScanManager {
+            pending: HashSet::new(),
+            statuses,
+            workers,
+            max_running: config.search_config().max_parallel_scans(),
+        }
+    }
+
+    fn configure(
+        &mut self,
+        directories: HashSet<PathBuf>,
+        max_running: Option<usize>,
+    ) -> Vec<PathBuf> {
+        let mut added = Vec::new();
+
+        self.statuses
+            .retain(|directory, _| directories.contains(directory));
+        self.pending
+            .retain(|directory| directories.contains(directory));
+
+        for directory in directories {
+            self.statuses.entry(directory.clone()).or_default();
+
+            if self.workers.insert(directory.clone()) {
+                added.push(directory);
+            }
+        }
+
+        self.max_running = max_running;
+
+        added
+    }
+
+    fn has_free_slot(&self) -> bool {
+        match self.max_running {
+            Some(max_running) => {
+                self.statuses
+                    .values()
+                    .filter(|status| status.in_progress)
+                    .count()
+                    < max_running
+            }
+            None => true,
+        }
+    }
+
+    fn request_rescan(&mut self, directory: Option<&Path>) -> ScanManagerResult<()> {
+        match directory {
+            Some(directory) if self.statuses.contains_key(directory) => {
+                debug!("Rescan requested for {}", directory.display());
+
+                self.pending.insert(directory.into());
+            }
+            Some(directory) => {
+                warn!("Directory {} is not configured", directory.display());
+
+                return Err(ScanManagerError::new("Directory is not configured"));
+            }
+            None => {
+                debug!("Rescan requested for all directories");
+
+                self.pending.extend(self.statuses.keys().cloned());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn scan_started(&mut self, directory: &Path) {
+        if let Some(status) = self.statuses.get_mut(directory) {
+            status.in_progress = true;
+            status.started = Some(OffsetDateTime::now_utc().unix_timestamp());
+            status.started_instant = Some(Instant::now());
+        }
+    }
+
+    fn scan_finished(&mut self, directory: &Path, files_found: usize, errors: &[String]) {
+        if let Some(status) = self.statuses.get_mut(directory) {
+            status.in_progress = false;
+            status.finished = Some(OffsetDateTime::now_utc().unix_timestamp());
+            status.duration = status
+                .started_instant
+                .map(|started| started.elapsed().as_millis() as u64);
+            status.files_found = files_found;
+            status.errors_count = errors.len();
+            status.errors = errors.iter().take(MAX_ERRORS).cloned().collect();
+        }
+    }
+
+    fn for_each<F>(&self, mut callback: F)
+    where
+        F: FnMut(&Path, &ScanStatus),
+    {
+        for (directory, status) in &self.statuses {
+            callback(directory, status);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScanStatus {
+    in_progress: bool,
+    started: Option<i64>,
+    started_instant: Option<Instant>,
+    finished: Option<i64>,
+    duration: Option<u64>,
+    files_found: usize,
+    errors_count: usize,
+    errors: Vec<String>,
+}
+
+impl ScanStatus {
+    pub fn in_progress(&self) -> bool {
+        self.in_progress
+    }
+
+    pub fn started(&self) -> Option<i64> {
+        self.started
+    }
+
+    pub fn finished(&self) -> Option<i64> {
+        self.finished
+    }
+
+    /// Duration of the last complete scan in milliseconds.
+    pub fn duration(&self) -> Option<u64> {
+        self.duration
+    }
+
+    pub fn files_found(&self) -> usize {
+        self.files_found
+    }
+
+    pub fn errors_count(&self) -> usize {
+        self.errors_count
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+pub fn create(config: ConfigRef) -> ScanManagerRef {
+    ScanManagerRef {
+        inner: Arc::new((Mutex::new(ScanManager::new(config)), Condvar::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScanManager;
+    use std::collections::BTreeMap;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    fn paths(paths: &[&str]) -> HashSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn configure_shold_return_added_directories() {
+        let mut scanmanager = ScanManager {
+            pending: HashSet::new(),
+            statuses: BTreeMap::new(),
+            workers: HashSet::new(),
+            max_running: None,
+        };
+
+        scanmanager.configure(paths(&["/a", "/b"]), None);
+        scanmanager.request_rescan(None).unwrap();
+        scanmanager.scan_finished(&PathBuf::from("/a"), 3, &[]);
+
+        let added = scanmanager.configure(paths(&["/a", "/c"]), Some(1));
+
+        assert_eq!(vec![PathBuf::from("/c")], added);
+        assert_eq!(
+            paths(&["/a", "/c"]),
+            scanmanager.statuses.keys().cloned().collect()
+        );
+        assert_eq!(paths(&["/a"]), scanmanager.pending);
+        assert_eq!(3, scanmanager.statuses[&PathBuf::from("/a")].files_found());
+        assert_eq!(Some(1), scanmanager.max_running);
+
+        // Worker of removed directory is running until it notices removal.
+        assert_eq!(paths(&["/a", "/b", "/c"]), scanmanager.workers);
+        assert!(scanmanager.configure(paths(&["/a", "/b"]), None).is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+use super::SchedulerError;
+use super::SchedulerResult;
+use time::Date;
+use time::Duration;
+use time::OffsetDateTime;
+use time::UtcOffset;
+
+/// Number of days to look ahead for next run, covers February 29 schedules.
+const MAX_DAYS: usize = 366 * 8;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Five field cron expression: minute, hour, day of month, month and day of week. Fields
+/// support `*`, lists, ranges, steps and month or weekday names. Day of month and day of week
+/// combined by OR if both are restricted, as in cron.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> SchedulerResult<CronSchedule> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields: Vec<_> = expression.split_whitespace().collect();
+
+        if fields.len() != 5 {
+            return Err(SchedulerError::format(format_args!(
+                "Cron expression must have 5 fields, but {} given",
+                fields.len()
+            )));
+        }
+
+        // Sunday may be written as 7.
+        let weekdays = parse_field(fields[4], 0, 7, &WEEKDAY_NAMES)?;
+        let weekdays = (weekdays | (weekdays >> 7)) & 0x7f;
+
+        Ok(CronSchedule {
+            minutes: parse_field(fields[0], 0, 59, &[])?,
+            hours: parse_field(fields[1], 0, 23, &[])?,
+            days: parse_field(fields[2], 1, 31, &[])?,
+            months: parse_field(fields[3], 1, 12, &MONTH_NAMES)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    /// Returns first time matching schedule strictly after `time`, seconds are ignored.
+    pub fn next_after(&self, time: OffsetDateTime) -> Option<OffsetDateTime> {
+        let time = time.to_offset(UtcOffset::UTC);
+        let start =
+            time.date().with_hms(time.hour(), time.minute(), 0).ok()? + Duration::minutes(1);
+        let mut date = start.date();
+
+        for _ in 0..MAX_DAYS {
+            if self.matches_date(date) {
+                let (first_hour, first_minute) = if date == start.date() {
+                    (start.hour(), start.minute())
+                } else {
+                    (0, 0)
+                };
+
+                for hour in (first_hour..24).filter(|&h| has_bit(self.hours, h)) {
+                    let from = if hour == first_hour { first_minute } else { 0 };
+
+                    if let Some(minute) = (from..60).find(|&m| has_bit(self.minutes, m)) {
+                        return date
+                            .with_hms(hour, minute, 0)
+                            .ok()
+                            .map(|time| time.assume_utc());
+                    }
+                }
+            }
+
+            date = date.next_day()?;
+        }
+
+        None
+    }
+
+    fn matches_date(&self, date: Date) -> bool {
+        let day = has_bit(self.days, date.day());
+        let weekday = has_bit(self.weekdays, date.weekday().number_days_from_sunday());
+
+        if !has_bit(self.months, date.month() as u8) {
+            return false;
+        }
+
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+fn has_bit(mask: u64, bit: u8) -> bool {
+    mask & (1 << bit) != 0
+}
+
+fn parse_field(field: &str, min: u8, max: u8, names: &[&str]) -> SchedulerResult<u64> {
+    let mut mask = 0;
+
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, parse_step(step)?),
+            None => (item, 1),
+        };
+        let (from, to) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((from, to)) => (
+                    parse_value(from, min, max, names)?,
+                    parse_value(to, min, max, names)?,
+                ),
+                // Single value with step means range up to maximum, like `5/15`.
+                None if step > 1 => (parse_value(range, min, max, names)?, max),
+                None => {
+                    let value = parse_value(range, min, max, names)?;
+
+                    (value, value)
+                }
+            },
+        };
+
+        if from > to {
+            return Err(SchedulerError::format(format_args!(
+                "Invalid cron range {}",
+                item
+            )));
+        }
+
+        for value in (from..=to).step_by(step) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_step(step: &str) -> SchedulerResult<usize> {
+    match step.parse() {
+        Ok(step) if step > 0 => Ok(step),
+        _ => Err(SchedulerError::format(format_args!(
+            "Invalid cron step {}",
+            step
+        ))),
+    }
+}
+
+fn parse_value(value: &str, min: u8, max: u8, names: &[&str]) -> SchedulerResult<u8> {
+    let lower = value.to_lowercase();
+
+    if let Some(index) = names.iter().position(|name| *name == lower) {
+        return Ok(index as u8 + min);
+    }
+
+    match value.parse() {
+        Ok(value) if value >= min && value <= max => Ok(value),
+        _ => Err(SchedulerError::format(format_args!(
+            "Invalid cron value {}, must be between {} and {}",
+            value, min, max
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CronSchedule;
+    use time::Date;
+    use time::Month;
+    use time::OffsetDateTime;
+
+    fn time(year: i32, month: Month, day: u8, hour: u8, minute: u8) -> OffsetDateTime {
+        Date::from_calendar_date(year, month, day)
+            .unwrap()
+            .with_hms(hour, minute, 0)
+            .unwrap()
+            .assume_utc()
+    }
+
+    fn next(expression: &str, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        CronSchedule::parse(expression).unwrap().next_after(after)
+    }
+
+    #[test]
+    fn next_shold_run_every_minute() {
+        assert_eq!(
+            Some(time(2024, Month::March, 1, 10, 16)),
+            next("* * * * *", time(2024, Month::March, 1, 10, 15))
+        );
+    }
+
+    #[test]
+    fn next_shold_run_daily() {
+        assert_eq!(
+            Some(time(2024, Month::March, 2, 2, 30)),
+            next("30 2 * * *", time(2024, Month::March, 1, 2, 30))
+        );
+        assert_eq!(
+            Some(time(2024, Month::March, 1, 2, 30)),
+            next("30 2 * * *", time(2024, Month::March, 1, 1, 59))
+        );
+    }
+
+    #[test]
+    fn next_shold_support_steps_and_ranges() {
+        assert_eq!(
+            Some(time(2024, Month::March, 1, 10, 45)),
+            next("*/15 9-17 * * *", time(2024, Month::March, 1, 10, 31))
+        );
+        assert_eq!(
+            Some(time(2024, Month::March, 2, 9, 0)),
+            next("*/15 9-17 * * *", time(2024, Month::March, 1, 17, 45))
+        );
+    }
+
+    #[test]
+    fn next_shold_support_weekdays() {
+        // 2024-03-01 is Friday.
+        assert_eq!(
+            Some(time(2024, Month::March, 4, 3, 0)),
+            next("0 3 * * mon-fri", time(2024, Month::March, 1, 4, 0))
+        );
+        assert_eq!(
+            Some(time(2024, Month::March, 3, 0, 0)),
+            next("0 0 * * 7", time(2024, Month::March, 1, 4, 0))
+        );
+    }
+
+    #[test]
+    fn next_shold_combine_day_and_weekday() {
+        assert_eq!(
+            Some(time(2024, Month::March, 4, 0, 0)),
+            next("0 0 15 * mon", time(2024, Month::March, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn next_shold_find_leap_day() {
+        assert_eq!(
+            Some(time(2028, Month::February, 29, 0, 0)),
+            next("0 0 29 feb *", time(2024, Month::March, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn next_shold_support_aliases() {
+        assert_eq!(
+            CronSchedule::parse("0 0 * * *").unwrap(),
+            CronSchedule::parse("@daily").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_shold_reject_invalid() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("* * 0 * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("10-5 * * * *").is_err());
+        assert!(CronSchedule::parse("* * * foo *").is_err());
+    }
+}
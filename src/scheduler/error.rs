@@ -0,0 +1,34 @@
+use std::error::Error;
+use std::fmt::Arguments;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+pub type SchedulerResult<T> = Result<T, SchedulerError>;
+
+#[derive(Debug)]
+pub struct SchedulerError {
+    message: String,
+}
+
+impl SchedulerError {
+    pub fn new(message: &str) -> SchedulerError {
+        SchedulerError {
+            message: message.into(),
+        }
+    }
+
+    pub fn format(args: Arguments) -> SchedulerError {
+        SchedulerError {
+            message: format!("{}", args),
+        }
+    }
+}
+
+impl Error for SchedulerError {}
+
+impl Display for SchedulerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
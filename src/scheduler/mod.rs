@@ -0,0 +1,321 @@
+mod cron;
+mod error;
+
+pub use self::cron::CronSchedule;
+pub use self::error::SchedulerError;
+pub use self::error::SchedulerResult;
+
+use crate::config::ConfigRef;
+use crate::config::Schedule as ScheduleConfig;
+use crate::launcher::Launcher;
+use crate::launcher::RestoreRequest;
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::thread;
+use std::thread::Builder;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Maximal sleep between checks, keeps scheduler accurate after system clock changes.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct SchedulerRef {
+    inner: Arc<Mutex<Scheduler>>,
+}
+
+impl SchedulerRef {
+    fn lock(&self) -> SchedulerResult<MutexGuard<'_, Scheduler>> {
+        match self.inner.lock() {
+            Ok(scheduler) => Ok(scheduler),
+            Err(err) => {
+                warn!("Failed to acquire lock - {}", err);
+
+                Err(SchedulerError::new("Failed to acquire lock"))
+            }
+        }
+    }
+
+    pub fn for_each<F>(&self, callback: F) -> SchedulerResult<()>
+    where
+        F: FnMut(&Schedule),
+    {
+        self.lock()?.schedules.iter().for_each(callback);
+
+        Ok(())
+    }
+
+    /// Replaces schedules by schedules of reloaded configuration, keeps last run of schedules
+    /// with the same name.
+    pub fn configure(&self, config: ConfigRef) -> SchedulerResult<()> {
+        let mut scheduler = Scheduler::new(config);
+        let mut current = self.lock()?;
+
+        scheduler.keep_runs(&mut current);
+        *current = scheduler;
+
+        Ok(())
+    }
+
+    /// Returns requests of schedules which are due at `now` and moves them to next run.
+    fn take_due(&self, now: OffsetDateTime) -> SchedulerResult<Vec<(String, RestoreRequest)>> {
+        let mut scheduler = self.lock()?;
+        let mut result = Vec::new();
+
+        for schedule in &mut scheduler.schedules {
+            match schedule.next_run {
+                Some(next_run) if next_run <= now => {
+                    schedule.next_run = schedule.cron.next_after(now);
+                    result.push((schedule.name.clone(), schedule.request.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn record_run(
+        &self,
+        name: &str,
+        started: OffsetDateTime,
+        result: &Result<usize, String>,
+    ) -> SchedulerResult<()> {
+        if let Some(schedule) = self.lock()?.find_mut(name) {
+            schedule.last_run = Some(started.unix_timestamp());
+
+            match result {
+                Ok(job_id) => {
+                    schedule.last_job_id = Some(*job_id);
+                    schedule.last_error = None;
+                }
+                Err(err) => schedule.last_error = Some(err.clone()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn next_run(&self) -> SchedulerResult<Option<OffsetDateTime>> {
+        Ok(self
+            .lock()?
+            .schedules
+            .iter()
+            .filter_map(|schedule| schedule.next_run)
+            .min())
+    }
+}
+
+#[derive(Debug)]
+struct Scheduler {
+    schedules: Vec<Schedule>,
+}
+
+impl Scheduler {
+    /// Creates schedules of configuration, destinations of requests are replaced by references
+    /// which do not depend on order of destinations.
+    #[allow(clippy::needless_pass_by_value)]
+    fn new(config: ConfigRef) -> Scheduler {
+        let mut scheduler = Scheduler::with_schedules(config.schedules());
+
+        for schedule in &mut scheduler.schedules {
+            if let Some(request) = schedule.request.clone().with_canonical_destination(&config) {
+                schedule.request = request;
+            }
+        }
+
+        scheduler
+    }
+
+    fn with_schedules(schedules: &[ScheduleConfig]) -> Scheduler {
+        let now = OffsetDateTime::now_utc();
+        let schedules = schedules
+            .iter()
+            .filter_map(|schedule| match CronSchedule::parse(schedule.cron()) {
+                Ok(cron) => Some(Schedule {
+                    name: schedule.name().into(),
+                    expression: schedule.cron().into(),
+                    next_run: if schedule.enabled() {
+                        cron.next_after(now)
+                    } else {
+                        None
+                    },
+                    cron,
+                    enabled: schedule.enabled(),
+                    request: schedule.request().clone(),
+                    last_run: None,
+                    last_job_id: None,
+                    last_error: None,
+                }),
+                Err(err) => {
+                    warn!("Schedule {} skipped - {}", schedule.name(), err);
+
+                    None
+                }
+            })
+            .collect();
+
+        Scheduler { schedules }
+    }
+
+    /// Moves last run of schedules with the same name from `previous`.
+    fn keep_runs(&mut self, previous: &mut Scheduler) {
+        for schedule in &mut self.schedules {
+            if let Some(previous) = previous.find_mut(&schedule.name) {
+                schedule.last_run = previous.last_run;
+                schedule.last_job_id = previous.last_job_id;
+                schedule.last_error = previous.last_error.take();
+            }
+        }
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut Schedule> {
+        self.schedules
+            .iter_mut()
+            .find(|schedule| schedule.name == name)
+    }
+}
+
+#[derive(Debug)]
+pub struct Schedule {
+    name: String,
+    expression: String,
+    cron: CronSchedule,
+    enabled: bool,
+    request: RestoreRequest,
+    next_run: Option<OffsetDateTime>,
+    last_run: Option<i64>,
+    last_job_id: Option<usize>,
+    last_error: Option<String>,
+}
+
+impl Schedule {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn request(&self) -> &RestoreRequest {
+        &self.request
+    }
+
+    pub fn next_run(&self) -> Option<i64> {
+        self.next_run.map(|next_run| next_run.unix_timestamp())
+    }
+
+    pub fn last_run(&self) -> Option<i64> {
+        self.last_run
+    }
+
+    pub fn last_job_id(&self) -> Option<usize> {
+        self.last_job_id
+    }
+
+    pub fn last_error(&self) -> Option<&String> {
+        self.last_error.as_ref()
+    }
+}
+
+fn run(scheduler: &SchedulerRef, launcher: &Launcher) -> SchedulerResult<()> {
+    loop {
+        let now = OffsetDateTime::now_utc();
+
+        for (name, request) in scheduler.take_due(now)? {
+            let result = launcher.launch(request).map_err(|err| err.to_string());
+
+            match result {
+                Ok(job_id) => info!("Scheduled restore started, job {}", job_id),
+                Err(ref err) => warn!("Failed to start scheduled restore - {}", err),
+            }
+
+            scheduler.record_run(&name, now, &result)?;
+        }
+
+        // Scheduler keeps running without active schedules, they may be added by reload.
+        let sleep = match scheduler.next_run()? {
+            Some(next_run) => {
+                let duration = next_run - OffsetDateTime::now_utc();
+
+                Duration::try_from(duration)
+                    .unwrap_or(Duration::ZERO)
+                    .min(MAX_SLEEP)
+            }
+            None => MAX_SLEEP,
+        };
+
+        thread::sleep(sleep);
+    }
+}
+
+pub fn start(scheduler: SchedulerRef, launcher: Launcher) {
+    if let Err(err) = Builder::new().name("scheduler".to_string()).spawn(move || {
+        if let Err(err) = run(&scheduler, &launcher) {
+            warn!("Scheduler stopped - {}", err);
+        }
+    }) {
+        warn!("Failed to start scheduler - {}", err);
+    }
+}
+
+pub fn create(config: ConfigRef) -> SchedulerRef {
+    SchedulerRef {
+        inner: Arc::new(Mutex::new(Scheduler::new(config))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scheduler;
+
+    fn scheduler(names: &[&str]) -> Scheduler {
+        let schedules: Vec<_> = names
+            .iter()
+            .map(|name| {
+                format!(
+                    "- {{name: {}, cron: \"0 3 * * *\", request: {{destination: 0, \
+                     backup: {{type: Path, path: /tmp/{}.backup}}, database: DropAndCreate, \
+                     restore: {{type: Full}}, ignore_errors: false}}}}",
+                    name, name
+                )
+            })
+            .collect();
+
+        Scheduler::with_schedules(&serde_yaml::from_str::<Vec<_>>(&schedules.join("\n")).unwrap())
+    }
+
+    #[test]
+    fn keep_runs_shold_keep_runs_of_schedules_with_same_name() {
+        let mut previous = scheduler(&["sales", "stock"]);
+        let mut current = scheduler(&["stock", "users"]);
+
+        for schedule in &mut previous.schedules {
+            schedule.last_run = Some(100);
+            schedule.last_job_id = Some(1);
+            schedule.last_error = Some("failed".into());
+        }
+
+        current.keep_runs(&mut previous);
+
+        let stock = current.find_mut("stock").unwrap();
+
+        assert_eq!(Some(100), stock.last_run());
+        assert_eq!(Some(1), stock.last_job_id());
+        assert_eq!(Some(&"failed".to_string()), stock.last_error());
+        assert!(stock.next_run().is_some());
+
+        let users = current.find_mut("users").unwrap();
+
+        assert_eq!(None, users.last_run());
+        assert_eq!(None, users.last_job_id());
+        assert!(current.find_mut("sales").is_none());
+    }
+}
@@ -1,43 +1,134 @@
 use crate::config::ConfigRef;
 use crate::config::Cors;
+use crate::configmanager::ConfigManagerRef;
+use crate::configmanager::Reloader;
+use crate::databasemanager::DatabaseManager;
+use crate::expirymanager::ExpiryManagerRef;
 use crate::handler::AbortHandler;
+use crate::handler::BackupsHandler;
+use crate::handler::BatchRestoreHandler;
+use crate::handler::CheckDestinationsHandler;
+use crate::handler::CreatePresetHandler;
+use crate::handler::DatabasesHandler;
+use crate::handler::DeletePresetHandler;
+use crate::handler::DropDatabaseHandler;
+use crate::handler::ExpiryHandler;
+use crate::handler::ExtendExpiryHandler;
 use crate::handler::JobsHandler;
+use crate::handler::LaunchPresetHandler;
+use crate::handler::PresetsHandler;
+use crate::handler::ReloadConfigHandler;
+use crate::handler::RerunHandler;
+use crate::handler::RescanHandler;
 use crate::handler::RestoreHandler;
+use crate::handler::ScanStatusHandler;
+use crate::handler::SchedulesHandler;
 use crate::handler::SearchHandler;
 use crate::handler::SettingsHandler;
 use crate::handler::StatusHandler;
-use crate::http::HttpClientRef;
+use crate::handler::SuggestHandler;
+use crate::handler::UpdatePresetHandler;
+use crate::healthmanager::HealthManagerRef;
 use crate::jobmanager::JobManagerRef;
+use crate::launcher::Launcher;
 use crate::options::Options;
 use crate::pathmanager::PathManagerRef;
+use crate::presetmanager::PresetManagerRef;
+use crate::scanmanager::ScanManagerRef;
+use crate::scheduler::SchedulerRef;
 use iron::Chain;
 use iron::Iron;
 use iron_cors::CorsMiddleware;
 use mount::Mount;
 use staticfile::Static;
 
-#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 pub fn start(
     options: &Options,
-    config: ConfigRef,
+    config_manager: ConfigManagerRef,
     job_manager: JobManagerRef,
     path_manager: PathManagerRef,
-    http_client: HttpClientRef,
+    scan_manager: ScanManagerRef,
+    scheduler: SchedulerRef,
+    preset_manager: PresetManagerRef,
+    health_manager: HealthManagerRef,
+    database_manager: DatabaseManager,
+    expiry_manager: ExpiryManagerRef,
+    launcher: Launcher,
+    reloader: Reloader,
 ) {
     let mut mount = Mount::new();
-    mount.mount("/api/v3/settings", SettingsHandler::new(config.clone()));
     mount.mount(
-        "/api/v3/restore",
-        RestoreHandler::new(config.clone(), job_manager.clone(), http_client.clone()),
+        "/api/v3/settings",
+        SettingsHandler::new(config_manager.clone(), health_manager.clone()),
+    );
+    mount.mount(
+        "/api/v3/destinations/check",
+        CheckDestinationsHandler::new(config_manager.clone(), health_manager.clone()),
+    );
+    mount.mount(
+        "/api/v3/databases",
+        DatabasesHandler::new(database_manager.clone()),
+    );
+    mount.mount(
+        "/api/v3/databases/drop",
+        DropDatabaseHandler::new(
+            config_manager.clone(),
+            database_manager.clone(),
+            expiry_manager.clone(),
+        ),
+    );
+    mount.mount("/api/v3/expiry", ExpiryHandler::new(expiry_manager.clone()));
+    mount.mount(
+        "/api/v3/expiry/extend",
+        ExtendExpiryHandler::new(config_manager.clone(), expiry_manager.clone()),
+    );
+    mount.mount("/api/v3/restore", RestoreHandler::new(launcher.clone()));
+    mount.mount(
+        "/api/v3/restore/batch",
+        BatchRestoreHandler::new(launcher.clone()),
     );
     mount.mount("/api/v3/abort", AbortHandler::new(job_manager.clone()));
+    mount.mount("/api/v3/rerun", RerunHandler::new(launcher.clone()));
     mount.mount("/api/v3/status", StatusHandler::new(job_manager.clone()));
     mount.mount("/api/v3/jobs", JobsHandler::new(job_manager.clone()));
     mount.mount("/api/v3/search", SearchHandler::new(path_manager.clone()));
+    mount.mount("/api/v3/suggest", SuggestHandler::new(path_manager.clone()));
+    mount.mount("/api/v3/backups", BackupsHandler::new(path_manager.clone()));
+    mount.mount("/api/v3/rescan", RescanHandler::new(scan_manager.clone()));
+    mount.mount(
+        "/api/v3/scanstatus",
+        ScanStatusHandler::new(scan_manager.clone()),
+    );
+    mount.mount(
+        "/api/v3/schedules",
+        SchedulesHandler::new(scheduler.clone()),
+    );
+    mount.mount(
+        "/api/v3/presets",
+        PresetsHandler::new(preset_manager.clone()),
+    );
+    mount.mount(
+        "/api/v3/presets/create",
+        CreatePresetHandler::new(preset_manager.clone(), launcher.clone()),
+    );
+    mount.mount(
+        "/api/v3/presets/update",
+        UpdatePresetHandler::new(preset_manager.clone(), launcher.clone()),
+    );
+    mount.mount(
+        "/api/v3/presets/delete",
+        DeletePresetHandler::new(preset_manager.clone()),
+    );
+    mount.mount(
+        "/api/v3/presets/launch",
+        LaunchPresetHandler::new(preset_manager.clone(), launcher.clone()),
+    );
+    mount.mount("/api/v3/config/reload", ReloadConfigHandler::new(reloader));
     mount.mount("/static", Static::new("public/static"));
     mount.mount("/", Static::new("public"));
 
-    let chain = make_chain(&config, mount);
+    let chain = make_chain(&config_manager.get(), mount);
     let address = options.address();
     let port = options.port();
 
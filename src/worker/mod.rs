@@ -1,10 +1,10 @@
 mod restore;
 mod search;
 
+pub use self::restore::DatabaseDescription;
+pub use self::restore::DatabaseError;
+pub use self::restore::PostgreSQL;
 pub use self::restore::Worker as RestoreWorker;
-pub use self::restore::WorkerError as RestoreWorkerError;
-pub use self::restore::WorkerResult as RestoreWorkerResult;
+pub use self::restore::MAINTENANCE_DATABASE;
 pub use self::search::start as start_search;
-pub use self::search::Worker as SearchWorker;
-pub use self::search::WorkerError as SearchWorkerError;
-pub use self::search::WorkerResult as SearchWorkerResult;
+pub use self::search::start_directories as start_search_directories;
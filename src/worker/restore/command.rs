@@ -1,5 +1,9 @@
 use super::error::WorkerError;
 use super::error::WorkerResult;
+use super::postgres::quote_identifier;
+use super::TableDescription;
+use crate::config::ClientCertificate;
+use crate::config::SslMode;
 use crate::jobmanager::Job;
 use crate::jobmanager::JobManagerRef;
 use crate::jobmanager::JobStatus;
@@ -32,6 +36,41 @@ impl<'a> WorkerCommand<'a> {
             .ok_or_else(|| WorkerError::new("Job not found"))
     }
 
+    /// Returns environment of commands with password and SSL settings of destination.
+    fn environment(&self) -> Vec<(&'static str, &str)> {
+        let mut environment = vec![
+            ("PGPASSWORD", self.settings.password()),
+            ("PGSSLMODE", self.settings.ssl_mode().as_str()),
+        ];
+
+        if let Some(root_certificate) = self.settings.root_certificate() {
+            environment.push(("PGSSLROOTCERT", root_certificate));
+        }
+
+        if let Some(client_certificate) = self.settings.client_certificate() {
+            environment.push(("PGSSLCERT", client_certificate.certificate()));
+            environment.push(("PGSSLKEY", client_certificate.key()));
+        }
+
+        environment
+    }
+
+    /// Returns `pg_restore` arguments skipping owners and privileges unless destination
+    /// keeps them.
+    fn ownership_args(&self) -> Vec<&'static str> {
+        let mut args = Vec::new();
+
+        if !self.settings.keep_owner() {
+            args.push("--no-owner");
+        }
+
+        if !self.settings.keep_privileges() {
+            args.push("--no-privileges");
+        }
+
+        args
+    }
+
     fn wait_command(&self, mut command: Command) -> WorkerResult<CommandStatus> {
         if self.is_aborted()? {
             return Ok(CommandStatus::Aborted);
@@ -80,7 +119,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--host")
             .arg(self.settings.host())
             .arg("--port")
@@ -92,7 +131,7 @@ impl<'a> WorkerCommand<'a> {
             command.arg("--template").arg(template);
         }
 
-        command.arg(&self.settings.database_name());
+        command.arg(self.settings.database_name());
 
         self.wait_command(command)
     }
@@ -109,7 +148,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--host")
             .arg(self.settings.host())
             .arg("--port")
@@ -117,7 +156,7 @@ impl<'a> WorkerCommand<'a> {
             .arg("--username")
             .arg(self.settings.role())
             .arg("--if-exists")
-            .arg(&self.settings.database_name());
+            .arg(self.settings.database_name());
 
         self.wait_command(command)
     }
@@ -138,7 +177,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--verbose")
             .arg("--host")
             .arg(self.settings.host())
@@ -147,18 +186,17 @@ impl<'a> WorkerCommand<'a> {
             .arg("--username")
             .arg(self.settings.role())
             .arg("--dbname")
-            .arg(&self.settings.database_name());
+            .arg(self.settings.database_name());
 
         if clean {
             command.arg("--clean");
         }
 
         command
-            .arg("--no-owner")
-            .arg("--no-privileges")
+            .args(self.ownership_args())
             .arg("--jobs")
             .arg(format!("{}", self.settings.restore_jobs()))
-            .arg(&backup_path);
+            .arg(backup_path);
 
         self.wait_command(command)
     }
@@ -184,7 +222,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--verbose")
             .arg("--host")
             .arg(self.settings.host())
@@ -193,12 +231,11 @@ impl<'a> WorkerCommand<'a> {
             .arg("--username")
             .arg(self.settings.role())
             .arg("--dbname")
-            .arg(&self.settings.database_name())
+            .arg(self.settings.database_name())
             .arg("--schema")
             .arg(name)
             .arg("--schema-only")
-            .arg("--no-owner")
-            .arg("--no-privileges")
+            .args(self.ownership_args())
             .arg("--jobs")
             .arg(format!("{}", self.settings.restore_jobs()))
             .arg(backup_path);
@@ -227,7 +264,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--verbose")
             .arg("--host")
             .arg(self.settings.host())
@@ -236,11 +273,10 @@ impl<'a> WorkerCommand<'a> {
             .arg("--username")
             .arg(self.settings.role())
             .arg("--dbname")
-            .arg(&self.settings.database_name())
+            .arg(self.settings.database_name())
             .arg("--schema")
             .arg(name)
-            .arg("--no-owner")
-            .arg("--no-privileges")
+            .args(self.ownership_args())
             .arg("--jobs")
             .arg(format!("{}", self.settings.restore_jobs()))
             .arg(backup_path);
@@ -271,7 +307,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--verbose")
             .arg("--host")
             .arg(self.settings.host())
@@ -280,13 +316,12 @@ impl<'a> WorkerCommand<'a> {
             .arg("--username")
             .arg(self.settings.role())
             .arg("--dbname")
-            .arg(&self.settings.database_name())
+            .arg(self.settings.database_name())
             .arg("--schema")
             .arg(schema)
             .arg("--table")
             .arg(table)
-            .arg("--no-owner")
-            .arg("--no-privileges")
+            .args(self.ownership_args())
             .arg(backup_path);
 
         self.wait_command(command)
@@ -315,7 +350,7 @@ impl<'a> WorkerCommand<'a> {
 
         command
             .env_clear()
-            .env("PGPASSWORD", self.settings.password())
+            .envs(self.environment())
             .arg("--verbose")
             .arg("--host")
             .arg(self.settings.host())
@@ -324,17 +359,58 @@ impl<'a> WorkerCommand<'a> {
             .arg("--username")
             .arg(self.settings.role())
             .arg("--dbname")
-            .arg(&self.settings.database_name())
+            .arg(self.settings.database_name())
             .arg("--schema")
             .arg(schema)
             .arg("--index")
             .arg(index)
-            .arg("--no-owner")
-            .arg("--no-privileges")
+            .args(self.ownership_args())
             .arg(backup_path);
 
         self.wait_command(command)
     }
+
+    /// Runs `vacuumdb --analyze-in-stages` for given tables or whole database.
+    pub fn analyze_in_stages(
+        &self,
+        tables: Option<&[TableDescription]>,
+    ) -> WorkerResult<CommandStatus> {
+        info!("Analyzing database {}", self.settings.database_name());
+
+        self.settings
+            .job_manager()
+            .set_stage(self.jobid, "Analyze database")
+            .map_err(WorkerError::set_stage_error)?;
+
+        let vacuumdb_path = self
+            .settings
+            .vacuumdb_path()
+            .ok_or_else(|| WorkerError::new("vacuumdb path not defined in configuration."))?;
+        let mut command = Command::new(vacuumdb_path);
+
+        command
+            .env_clear()
+            .envs(self.environment())
+            .arg("--host")
+            .arg(self.settings.host())
+            .arg("--port")
+            .arg(format!("{}", self.settings.port()))
+            .arg("--username")
+            .arg(self.settings.role())
+            .arg("--dbname")
+            .arg(self.settings.database_name())
+            .arg("--analyze-in-stages");
+
+        for table in tables.unwrap_or_default() {
+            command.arg("--table").arg(format!(
+                "{}.{}",
+                quote_identifier(table.schema()),
+                quote_identifier(table.name())
+            ));
+        }
+
+        self.wait_command(command)
+    }
 }
 
 fn to_job_paths(job: &Job) -> (PathBuf, PathBuf) {
@@ -363,6 +439,7 @@ pub trait WorkerSettings: Debug {
     fn createdb_path(&self) -> &str;
     fn dropdb_path(&self) -> &str;
     fn pgrestore_path(&self) -> &str;
+    fn vacuumdb_path(&self) -> Option<&String>;
     fn restore_jobs(&self) -> usize;
     fn job_manager(&self) -> &JobManagerRef;
     fn host(&self) -> &str;
@@ -370,5 +447,9 @@ pub trait WorkerSettings: Debug {
     fn role(&self) -> &str;
     fn password(&self) -> &str;
     fn database_name(&self) -> &str;
-    fn ignore_errors(&self) -> bool;
+    fn keep_owner(&self) -> bool;
+    fn keep_privileges(&self) -> bool;
+    fn ssl_mode(&self) -> SslMode;
+    fn root_certificate(&self) -> Option<&String>;
+    fn client_certificate(&self) -> Option<&ClientCertificate>;
 }
@@ -97,6 +97,12 @@ impl EntityList {
     pub fn tables(&self) -> &HashSet<TableDescription> {
         &self.tables
     }
+
+    /// Checks if table is restored as part of full schema or as separate table.
+    pub fn contains_table(&self, schema: &str, name: &str) -> bool {
+        self.full_schemas.contains(schema)
+            || self.tables.contains(&TableDescription::new(schema, name))
+    }
 }
 
 #[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Clone)]
@@ -0,0 +1,72 @@
+use crate::config::Destination;
+use crate::config::Hook;
+use glob::Pattern;
+use std::fs;
+
+/// Returns global and destination hooks matching database name, in order of execution.
+pub fn matching_hooks<'a>(
+    global: &'a [Hook],
+    destination: &'a Destination,
+    database_name: &str,
+) -> Vec<&'a Hook> {
+    global
+        .iter()
+        .chain(destination.hooks())
+        .filter(|hook| matches(hook, database_name))
+        .collect()
+}
+
+fn matches(hook: &Hook, database_name: &str) -> bool {
+    match hook.database_pattern() {
+        Some(pattern) => match Pattern::new(pattern) {
+            Ok(pattern) => pattern.matches(database_name),
+            Err(err) => {
+                warn!("Hook {} skipped - {}", hook.name(), err);
+
+                false
+            }
+        },
+        None => true,
+    }
+}
+
+/// Returns inline script or reads script file.
+pub fn read_script(hook: &Hook) -> Result<String, String> {
+    match (hook.sql(), hook.file()) {
+        (Some(sql), _) => Ok(sql.clone()),
+        (None, Some(file)) => fs::read_to_string(file)
+            .map_err(|err| format!("Failed to read hook file {} - {}", file, err)),
+        (None, None) => Err(format!("Hook {} has no script", hook.name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matching_hooks;
+    use crate::config::Destination;
+    use crate::config::Hook;
+
+    fn hooks(hooks: &str) -> Vec<Hook> {
+        serde_yaml::from_str(hooks).unwrap()
+    }
+
+    #[test]
+    fn matching_hooks_shold_filter_by_database_name() {
+        let global = hooks(
+            "[{name: all, sql: 'select 1'}, {name: qa, database_pattern: '*_qa', sql: 'select 2'}]",
+        );
+        let destination: Destination = serde_yaml::from_str(
+            "{host: localhost, port: 5432, role: postgres, password: pass, hooks: [{name: local, sql: 'select 3'}]}",
+        )
+        .unwrap();
+        let names = |database_name| {
+            matching_hooks(&global, &destination, database_name)
+                .iter()
+                .map(|hook| hook.name().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec!["all", "qa", "local"], names("sales_qa"));
+        assert_eq!(vec!["all", "local"], names("sales"));
+    }
+}
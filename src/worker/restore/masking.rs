@@ -0,0 +1,262 @@
+use super::entity::EntityList;
+use super::postgres::quote_identifier;
+use super::WorkerError;
+use super::WorkerResult;
+use crate::config::MaskingRule;
+use crate::config::MaskingStrategy;
+use glob::Pattern;
+use std::collections::BTreeMap;
+
+const KEEP_FORMAT_FROM: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const KEEP_FORMAT_TO: &str = "xxxxxxxxxxxxxxxxxxxxxxxxxxXXXXXXXXXXXXXXXXXXXXXXXXXX9999999999";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    schema: String,
+    table: String,
+    name: String,
+    data_type: String,
+    is_string: bool,
+    is_updatable: bool,
+}
+
+impl Column {
+    pub fn new(
+        schema: &str,
+        table: &str,
+        name: &str,
+        data_type: &str,
+        is_string: bool,
+        is_updatable: bool,
+    ) -> Column {
+        Column {
+            schema: schema.into(),
+            table: table.into(),
+            name: name.into(),
+            data_type: data_type.into(),
+            is_string,
+            is_updatable,
+        }
+    }
+
+    fn path(&self) -> String {
+        format!("{}.{}.{}", self.schema, self.table, self.name)
+    }
+}
+
+/// `UPDATE` statement masking columns of single table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskingStatement {
+    table: String,
+    sql: String,
+}
+
+impl MaskingStatement {
+    /// Quoted table name.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+/// Builds masking statements for columns of restored tables, single statement per table.
+/// Partial restores mask only restored tables. Columns which can not be updated are skipped,
+/// generated columns are computed from masked columns.
+pub fn statements(
+    rules: &[MaskingRule],
+    columns: &[Column],
+    restored: Option<&EntityList>,
+) -> WorkerResult<Vec<MaskingStatement>> {
+    let patterns: Vec<_> = rules
+        .iter()
+        .filter_map(|rule| match Pattern::new(rule.column()) {
+            Ok(pattern) => Some((pattern, rule.strategy())),
+            Err(err) => {
+                warn!("Masking rule {} skipped - {}", rule.column(), err);
+
+                None
+            }
+        })
+        .collect();
+    let mut tables: BTreeMap<(&str, &str), Vec<String>> = BTreeMap::new();
+
+    for column in columns {
+        if !column.is_updatable
+            || restored
+                .is_some_and(|restored| !restored.contains_table(&column.schema, &column.table))
+        {
+            continue;
+        }
+
+        let path = column.path();
+
+        if let Some((_, strategy)) = patterns.iter().find(|(pattern, _)| pattern.matches(&path)) {
+            tables
+                .entry((&column.schema, &column.table))
+                .or_default()
+                .push(assignment(column, strategy)?);
+        }
+    }
+
+    Ok(tables
+        .into_iter()
+        .map(|((schema, table), assignments)| {
+            let table = format!("{}.{}", quote_identifier(schema), quote_identifier(table));
+            let sql = format!("update {} set {}", table, assignments.join(", "));
+
+            MaskingStatement { table, sql }
+        })
+        .collect())
+}
+
+/// Returns assignment of masked value. Text values are cast to column type, so length of
+/// character types is kept, and are allowed only for character types and hashed UUIDs.
+fn assignment(column: &Column, strategy: &MaskingStrategy) -> WorkerResult<String> {
+    let name = quote_identifier(&column.name);
+    let (value, strategy_name) = match strategy {
+        MaskingStrategy::Null => return Ok(format!("{} = null", name)),
+        MaskingStrategy::Fixed { value } => {
+            return Ok(format!("{} = {}", name, quote_literal(value)))
+        }
+        MaskingStrategy::Hash => (format!("md5({}::text)", name), "Hash"),
+        MaskingStrategy::FakeEmail { domain } => (
+            format!(
+                "'user_' || substr(md5({}::text), 1, 12) || {}",
+                name,
+                quote_literal(&format!("@{}", domain))
+            ),
+            "FakeEmail",
+        ),
+        MaskingStrategy::KeepFormat => (
+            format!(
+                "translate({}::text, '{}', '{}')",
+                name, KEEP_FORMAT_FROM, KEEP_FORMAT_TO
+            ),
+            "KeepFormat",
+        ),
+    };
+
+    if column.is_string || (*strategy == MaskingStrategy::Hash && column.data_type == "uuid") {
+        Ok(format!("{} = ({})::{}", name, value, column.data_type))
+    } else {
+        Err(WorkerError::new(&format!(
+            "Masking strategy {} can not be applied to column {} of type {}, use Null or Fixed",
+            strategy_name,
+            column.path(),
+            column.data_type
+        )))
+    }
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::statements;
+    use super::Column;
+    use crate::config::MaskingRule;
+    use crate::worker::restore::entity::EntityList;
+
+    fn rules(rules: &str) -> Vec<MaskingRule> {
+        serde_yaml::from_str(rules).unwrap()
+    }
+
+    fn columns() -> Vec<Column> {
+        vec![
+            Column::new("public", "users", "email", "text", true, true),
+            Column::new(
+                "public",
+                "users",
+                "phone",
+                "character varying(20)",
+                true,
+                true,
+            ),
+            Column::new("public", "users", "id", "integer", false, false),
+            Column::new("app", "settings", "secret", "text", true, true),
+        ]
+    }
+
+    #[test]
+    fn statements_shold_group_columns_by_table() {
+        let rules = rules(
+            r#"
+            - {column: "*.users.email", strategy: {type: FakeEmail}}
+            - {column: "*.*.phone", strategy: {type: KeepFormat}}
+            - {column: "app.settings.*", strategy: {type: Fixed, value: "it's hidden"}}
+            "#,
+        );
+        let statements = statements(&rules, &columns(), None).unwrap();
+
+        assert_eq!(2, statements.len());
+        assert_eq!("\"app\".\"settings\"", statements[0].table());
+        assert_eq!(
+            "update \"app\".\"settings\" set \"secret\" = 'it''s hidden'",
+            statements[0].sql()
+        );
+        assert_eq!(
+            "update \"public\".\"users\" set \"email\" = ('user_' || substr(md5(\"email\"::text), 1, 12) || '@example.com')::text, \"phone\" = (translate(\"phone\"::text, 'abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789', 'xxxxxxxxxxxxxxxxxxxxxxxxxxXXXXXXXXXXXXXXXXXXXXXXXXXX9999999999'))::character varying(20)",
+            statements[1].sql()
+        );
+    }
+
+    #[test]
+    fn statements_shold_use_first_matched_rule() {
+        let rules = rules(
+            r#"
+            - {column: "public.users.email", strategy: {type: Null}}
+            - {column: "*.email", strategy: {type: Hash}}
+            "#,
+        );
+        let statements = statements(&rules, &columns(), None).unwrap();
+
+        assert_eq!(1, statements.len());
+        assert_eq!(
+            "update \"public\".\"users\" set \"email\" = null",
+            statements[0].sql()
+        );
+    }
+
+    #[test]
+    fn statements_shold_skip_not_restored_tables() {
+        let rules = rules(r#"[{column: "*", strategy: {type: Null}}]"#);
+        let restored = EntityList::parse(&["app.settings"]);
+        let statements = statements(&rules, &columns(), Some(&restored)).unwrap();
+
+        assert_eq!(1, statements.len());
+        assert_eq!("\"app\".\"settings\"", statements[0].table());
+    }
+
+    #[test]
+    fn statements_shold_check_column_types() {
+        let columns = vec![
+            Column::new("public", "users", "id", "integer", false, true),
+            Column::new("public", "users", "token", "uuid", false, true),
+            Column::new("public", "users", "total", "integer", false, false),
+        ];
+        let hash = rules(r#"[{column: "*.token", strategy: {type: Hash}}]"#);
+        let fixed = rules(r#"[{column: "*", strategy: {type: Fixed, value: "0"}}]"#);
+        let keep_format = rules(r#"[{column: "*.id", strategy: {type: KeepFormat}}]"#);
+
+        assert_eq!(
+            "update \"public\".\"users\" set \"token\" = (md5(\"token\"::text))::uuid",
+            statements(&hash, &columns, None).unwrap()[0].sql()
+        );
+        assert_eq!(
+            "update \"public\".\"users\" set \"id\" = '0', \"token\" = '0'",
+            statements(&fixed, &columns, None).unwrap()[0].sql()
+        );
+        assert_eq!(
+            "Masking strategy KeepFormat can not be applied to column public.users.id of type \
+             integer, use Null or Fixed",
+            statements(&keep_format, &columns, None)
+                .unwrap_err()
+                .message()
+        );
+    }
+}
@@ -1,13 +1,18 @@
 mod command;
 mod entity;
 mod error;
+mod hook;
 mod index;
+mod masking;
 mod postgres;
+mod retry;
 
 pub use self::error::WorkerError;
 pub use self::error::WorkerResult;
+pub use self::postgres::DatabaseDescription;
 pub use self::postgres::DatabaseError;
 pub use self::postgres::PostgreSQL;
+pub use self::postgres::MAINTENANCE_DATABASE;
 
 use self::command::CommandStatus;
 use self::command::WorkerCommand;
@@ -15,19 +20,33 @@ use self::command::WorkerSettings;
 use self::entity::EntityList;
 use self::entity::IndexDescription;
 use self::entity::TableDescription;
+use self::postgres::Scope;
+use self::retry::RetryPolicy;
+use crate::config::ClientCertificate;
 use crate::config::ConfigRef;
 use crate::config::Destination;
+use crate::config::DestinationRef;
+use crate::config::Hook;
+use crate::config::Maintenance;
+use crate::config::RetryStage;
+use crate::config::SslMode;
+use crate::expirymanager::ExpiryManagerRef;
 use crate::http::HttpClientRef;
 use crate::http::HttpClientResult;
 use crate::http::PathHandle;
 use crate::jobmanager::JobManagerRef;
+use crate::jobmanager::JobStatus;
 use std::collections::HashSet;
 use std::fmt::Arguments;
+use std::fmt::Display;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::thread;
 use std::thread::Builder;
+use std::time::Duration;
+use std::time::Instant;
 
 #[derive(Debug)]
 pub struct Worker {
@@ -36,6 +55,17 @@ pub struct Worker {
     destination: Destination,
     database_name: String,
     ignore_errors: bool,
+    terminate_connections: bool,
+    retry: RetryPolicy,
+    expiry: Option<Expiry>,
+}
+
+/// Expiry of created database, updated only after restore succeeds.
+#[derive(Debug)]
+struct Expiry {
+    expiry_manager: ExpiryManagerRef,
+    destination: DestinationRef,
+    ttl: Option<u64>,
 }
 
 impl Worker {
@@ -45,16 +75,39 @@ impl Worker {
         destination: &Destination,
         database_name: &str,
         ignore_errors: bool,
+        terminate_connections: bool,
     ) -> Worker {
+        let retry = RetryPolicy::new(config.retry());
+
         Worker {
             config,
             job_manager,
             destination: destination.clone(),
             database_name: database_name.into(),
             ignore_errors,
+            terminate_connections,
+            retry,
+            expiry: None,
         }
     }
 
+    /// Registers database with TTL after successful restore, or unregisters it if TTL is not
+    /// given, because database without TTL replaced registered one.
+    pub fn with_expiry(
+        mut self,
+        expiry_manager: ExpiryManagerRef,
+        destination: DestinationRef,
+        ttl: Option<u64>,
+    ) -> Worker {
+        self.expiry = Some(Expiry {
+            expiry_manager,
+            destination,
+            ttl,
+        });
+
+        self
+    }
+
     pub fn restore_file_full(
         self,
         jobid: usize,
@@ -69,6 +122,7 @@ impl Worker {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn restore_file_partial(
         self,
         jobid: usize,
@@ -112,6 +166,7 @@ impl Worker {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn restore_url_partial(
         self,
         jobid: usize,
@@ -150,25 +205,33 @@ impl Worker {
     ) -> WorkerResult<()> {
         let command = WorkerCommand::new(jobid, &self);
 
-        self.check_backup_path(jobid, &backup_path)?;
+        self.check_backup_path(jobid, backup_path)?;
 
         if drop_database {
-            self.execute_step(jobid, || command.drop_database())?;
+            self.drop_database(jobid, &command)?;
         }
 
         if create_database {
             let template = self.config.templates().full();
 
-            self.execute_step(jobid, || command.create_database(template))?;
+            self.execute_step(jobid, || {
+                self.execute_command(jobid, || command.create_database(template))
+            })?;
         }
 
         self.execute_step_soft(jobid, || {
             command.restore_backup(backup_path, !create_database)
         })?;
+        self.execute_step(jobid, || self.mask_columns(jobid, None))?;
+        self.execute_step_soft(jobid, || self.apply_ownership(jobid, None))?;
+        self.execute_step_soft(jobid, || self.execute_maintenance(jobid, &command, None))?;
+        self.execute_hooks(jobid)?;
+        self.update_expiry(jobid);
 
         self.set_complete(jobid, true)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn execute_backup_partial(
         self,
         jobid: usize,
@@ -181,10 +244,10 @@ impl Worker {
     ) -> WorkerResult<()> {
         let command = WorkerCommand::new(jobid, &self);
 
-        self.check_backup_path(jobid, &backup_path)?;
+        self.check_backup_path(jobid, backup_path)?;
 
         if drop_database {
-            self.execute_step(jobid, || command.drop_database())?;
+            self.drop_database(jobid, &command)?;
         }
 
         let entities = EntityList::parse(objects);
@@ -195,7 +258,9 @@ impl Worker {
         if create_database {
             let template = self.config.templates().partial();
 
-            self.execute_step(jobid, || command.create_database(template))?;
+            self.execute_step(jobid, || {
+                self.execute_command(jobid, || command.create_database(template))
+            })?;
         } else {
             self.execute_step(jobid, || self.cleanup_schemas(jobid, full_schemas))?;
         }
@@ -206,7 +271,7 @@ impl Worker {
         // Restore schema's related to required tables before restoring table.
         if restore_schema {
             for name in table_schemas {
-                self.execute_step_soft(jobid, || command.restore_schema_only(name, &backup_path))?;
+                self.execute_step_soft(jobid, || command.restore_schema_only(name, backup_path))?;
             }
         }
 
@@ -215,7 +280,7 @@ impl Worker {
 
         // Restore
         for name in full_schemas {
-            self.execute_step(jobid, || command.restore_schema_data(name, &backup_path))?;
+            self.execute_step(jobid, || command.restore_schema_data(name, backup_path))?;
         }
 
         // Drop required table to make sure that restored columns will be the same as in backup.
@@ -223,7 +288,7 @@ impl Worker {
 
         for table in tables {
             self.execute_step(jobid, || {
-                command.restore_table(table.schema(), table.name(), &backup_path)
+                command.restore_table(table.schema(), table.name(), backup_path)
             })?;
         }
 
@@ -234,9 +299,79 @@ impl Worker {
             })?;
         }
 
+        // Masking failure always fails job, unmasked data must not be reported as restored.
+        self.execute_step(jobid, || self.mask_columns(jobid, Some(&entities)))?;
+        self.execute_step_soft(jobid, || self.apply_ownership(jobid, Some(&entities)))?;
+        self.execute_step_soft(jobid, || {
+            self.execute_maintenance(jobid, &command, Some(&entities))
+        })?;
+        self.execute_hooks(jobid)?;
+        self.update_expiry(jobid);
+
         self.set_complete(jobid, true)
     }
 
+    /// Drops database, terminating connected sessions first if requested. Connections are
+    /// allowed again if database is not dropped.
+    fn drop_database(&self, jobid: usize, command: &WorkerCommand) -> WorkerResult<()> {
+        if !self.terminate_connections {
+            return self.execute_step(jobid, || {
+                self.execute_command(jobid, || command.drop_database())
+            });
+        }
+
+        let postgres = PostgreSQL::new(&self.destination, MAINTENANCE_DATABASE);
+        let result = self
+            .execute_step(jobid, || self.terminate_connections(jobid, &postgres))
+            .and_then(|_| {
+                self.execute_step(jobid, || {
+                    self.execute_command(jobid, || command.drop_database())
+                })
+            });
+
+        if result.is_err() {
+            if let Err(err) = postgres.allow_connections(&self.database_name) {
+                warn!(
+                    "Failed to allow connections to database {} - {}",
+                    self.database_name, err
+                );
+            }
+        }
+
+        result
+    }
+
+    fn terminate_connections(
+        &self,
+        jobid: usize,
+        postgres: &PostgreSQL,
+    ) -> WorkerResult<CommandStatus> {
+        info!("Terminating connections to database {}", self.database_name);
+
+        self.job_manager
+            .set_stage(jobid, "Terminate connections")
+            .map_err(WorkerError::set_stage_error)?;
+
+        if self.is_aborted(jobid) {
+            return Ok(CommandStatus::Aborted);
+        }
+
+        let sessions = self
+            .with_retry(
+                jobid,
+                RetryStage::Connection,
+                || postgres.terminate_connections(&self.database_name),
+                DatabaseError::is_connection_error,
+            )
+            .map_err(WorkerError::query_execution_error)?;
+
+        for session in sessions {
+            self.write_output(jobid, format_args!("Terminated session {}", session))?;
+        }
+
+        Ok(CommandStatus::Success)
+    }
+
     fn restore_indexes(
         &self,
         jobid: usize,
@@ -270,21 +405,19 @@ impl Worker {
         jobid: usize,
         schemas: &HashSet<String>,
     ) -> WorkerResult<CommandStatus> {
-        let postgres = PostgreSQL::new(
-            self.destination.host(),
-            self.destination.port(),
-            self.destination.role(),
-            self.destination.password(),
-            &self.database_name,
-        );
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
 
         self.job_manager
             .set_stage(jobid, "Creating schema's")
             .map_err(WorkerError::set_stage_error)?;
 
-        postgres
-            .create_schemas(schemas)
-            .map_err(WorkerError::query_execution_error)?;
+        self.with_retry(
+            jobid,
+            RetryStage::Connection,
+            || postgres.create_schemas(schemas),
+            DatabaseError::is_connection_error,
+        )
+        .map_err(WorkerError::query_execution_error)?;
 
         Ok(CommandStatus::Success)
     }
@@ -294,21 +427,19 @@ impl Worker {
         jobid: usize,
         schemas: &HashSet<String>,
     ) -> WorkerResult<CommandStatus> {
-        let postgres = PostgreSQL::new(
-            self.destination.host(),
-            self.destination.port(),
-            self.destination.role(),
-            self.destination.password(),
-            &self.database_name,
-        );
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
 
         self.job_manager
             .set_stage(jobid, "Cleaning schema's")
             .map_err(WorkerError::set_stage_error)?;
 
-        postgres
-            .drop_schemas(schemas)
-            .map_err(WorkerError::query_execution_error)?;
+        self.with_retry(
+            jobid,
+            RetryStage::Connection,
+            || postgres.drop_schemas(schemas),
+            DatabaseError::is_connection_error,
+        )
+        .map_err(WorkerError::query_execution_error)?;
 
         Ok(CommandStatus::Success)
     }
@@ -318,42 +449,297 @@ impl Worker {
         jobid: usize,
         tables: &HashSet<TableDescription>,
     ) -> WorkerResult<CommandStatus> {
-        let postgres = PostgreSQL::new(
-            self.destination.host(),
-            self.destination.port(),
-            self.destination.role(),
-            self.destination.password(),
-            &self.database_name,
-        );
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
 
         self.job_manager
             .set_stage(jobid, "Cleaning tables")
             .map_err(WorkerError::set_stage_error)?;
 
-        postgres
-            .drop_tables(tables)
+        self.with_retry(
+            jobid,
+            RetryStage::Connection,
+            || postgres.drop_tables(tables),
+            DatabaseError::is_connection_error,
+        )
+        .map_err(WorkerError::query_execution_error)?;
+
+        Ok(CommandStatus::Success)
+    }
+
+    fn mask_columns(
+        &self,
+        jobid: usize,
+        restored: Option<&EntityList>,
+    ) -> WorkerResult<CommandStatus> {
+        if self.config.masking().is_empty() {
+            return Ok(CommandStatus::Success);
+        }
+
+        self.job_manager
+            .set_stage(jobid, "Masking columns")
+            .map_err(WorkerError::set_stage_error)?;
+
+        if self.is_aborted(jobid) {
+            return Ok(CommandStatus::Aborted);
+        }
+
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
+        let columns = self
+            .with_retry(
+                jobid,
+                RetryStage::Connection,
+                || postgres.columns(),
+                DatabaseError::is_connection_error,
+            )
+            .map_err(WorkerError::query_execution_error)?;
+        let statements = masking::statements(self.config.masking(), &columns, restored)?;
+        let sql: Vec<_> = statements.iter().map(|statement| statement.sql()).collect();
+        let rows_updated = postgres
+            .execute_transaction(&sql)
+            .map_err(WorkerError::query_execution_error)?;
+
+        for (statement, rows_updated) in statements.iter().zip(rows_updated) {
+            self.write_output(
+                jobid,
+                format_args!("Masked {} rows of {}", rows_updated, statement.table()),
+            )?;
+        }
+
+        Ok(CommandStatus::Success)
+    }
+
+    /// Changes owner of restored objects and grants privileges to configured roles, whole
+    /// database for full restores and only restored schemas and tables for partial restores.
+    fn apply_ownership(
+        &self,
+        jobid: usize,
+        restored: Option<&EntityList>,
+    ) -> WorkerResult<CommandStatus> {
+        let owner = self.destination.owner().map(String::as_str);
+        let grants = self.destination.grants();
+
+        if owner.is_none() && grants.is_empty() {
+            return Ok(CommandStatus::Success);
+        }
+
+        self.job_manager
+            .set_stage(jobid, "Apply ownership and grants")
+            .map_err(WorkerError::set_stage_error)?;
+
+        if self.is_aborted(jobid) {
+            return Ok(CommandStatus::Aborted);
+        }
+
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
+        let (schemas, tables) = match restored {
+            Some(restored) => {
+                let mut schemas: Vec<_> = restored.full_schemas().iter().cloned().collect();
+                let mut tables: Vec<_> = restored.tables().iter().cloned().collect();
+
+                schemas.sort();
+                tables.sort_by(|a, b| (a.schema(), a.name()).cmp(&(b.schema(), b.name())));
+
+                (schemas, tables)
+            }
+            None => {
+                let schemas = self
+                    .with_retry(
+                        jobid,
+                        RetryStage::Connection,
+                        || postgres.schemas(),
+                        DatabaseError::is_connection_error,
+                    )
+                    .map_err(WorkerError::query_execution_error)?;
+
+                (schemas, Vec::new())
+            }
+        };
+        let statements = postgres
+            .apply_ownership(owner, grants, Scope::new(&schemas, &tables))
             .map_err(WorkerError::query_execution_error)?;
 
+        self.write_output(
+            jobid,
+            format_args!("Applied {} ownership and grant statements", statements),
+        )?;
+
         Ok(CommandStatus::Success)
     }
 
+    /// Updates statistics of restored database, only restored tables for partial restores.
+    fn execute_maintenance(
+        &self,
+        jobid: usize,
+        command: &WorkerCommand,
+        restored: Option<&EntityList>,
+    ) -> WorkerResult<CommandStatus> {
+        let maintenance = match self.config.maintenance() {
+            Some(maintenance) => maintenance,
+            None => return Ok(CommandStatus::Success),
+        };
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
+        let tables = match restored {
+            Some(restored) => {
+                let tables = self
+                    .with_retry(
+                        jobid,
+                        RetryStage::Connection,
+                        || postgres.tables(),
+                        DatabaseError::is_connection_error,
+                    )
+                    .map_err(WorkerError::query_execution_error)?;
+
+                Some(
+                    tables
+                        .into_iter()
+                        .filter(|table| restored.contains_table(table.schema(), table.name()))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            None => None,
+        };
+
+        if tables.as_ref().is_some_and(Vec::is_empty) {
+            return Ok(CommandStatus::Success);
+        }
+
+        match maintenance {
+            Maintenance::Analyze | Maintenance::VacuumAnalyze => {
+                let vacuum = maintenance == Maintenance::VacuumAnalyze;
+
+                info!("Analyzing database {}", self.database_name);
+
+                self.job_manager
+                    .set_stage(
+                        jobid,
+                        if vacuum {
+                            "Vacuum database"
+                        } else {
+                            "Analyze database"
+                        },
+                    )
+                    .map_err(WorkerError::set_stage_error)?;
+
+                if self.is_aborted(jobid) {
+                    return Ok(CommandStatus::Aborted);
+                }
+
+                postgres
+                    .analyze(tables.as_deref(), vacuum)
+                    .map_err(WorkerError::query_execution_error)?;
+
+                Ok(CommandStatus::Success)
+            }
+            Maintenance::AnalyzeInStages => {
+                self.execute_command(jobid, || command.analyze_in_stages(tables.as_deref()))
+            }
+        }
+    }
+
+    /// Executes post restore hooks, hook failures are ignored if job ignores errors.
+    fn execute_hooks(&self, jobid: usize) -> WorkerResult<()> {
+        let hooks =
+            hook::matching_hooks(self.config.hooks(), &self.destination, &self.database_name);
+
+        for hook in hooks {
+            self.execute_step_soft(jobid, || self.execute_hook(jobid, hook))?;
+        }
+
+        Ok(())
+    }
+
+    fn execute_hook(&self, jobid: usize, hook: &Hook) -> WorkerResult<CommandStatus> {
+        info!("Executing hook {} in {}", hook.name(), self.database_name);
+
+        self.job_manager
+            .set_stage(jobid, &format!("Run hook {}", hook.name()))
+            .map_err(WorkerError::set_stage_error)?;
+
+        if self.is_aborted(jobid) {
+            return Ok(CommandStatus::Aborted);
+        }
+
+        let script = match hook::read_script(hook) {
+            Ok(script) => script,
+            Err(err) => {
+                self.write_error(jobid, format_args!("{}", err))?;
+
+                return Ok(CommandStatus::Failed);
+            }
+        };
+        let postgres = PostgreSQL::new(&self.destination, &self.database_name);
+        let mut notices = Vec::new();
+        let result = self.with_retry(
+            jobid,
+            RetryStage::Connection,
+            || postgres.execute_script(&script, &mut notices),
+            DatabaseError::is_connection_error,
+        );
+
+        for notice in notices {
+            self.write_output(jobid, format_args!("{} - {}", hook.name(), notice))?;
+        }
+
+        match result {
+            Ok(()) => Ok(CommandStatus::Success),
+            Err(err) => {
+                self.write_error(jobid, format_args!("Hook {} failed - {}", hook.name(), err))?;
+
+                Ok(CommandStatus::Failed)
+            }
+        }
+    }
+
+    fn update_expiry(&self, jobid: usize) {
+        let expiry = match self.expiry {
+            Some(ref expiry) => expiry,
+            None => return,
+        };
+        let result = match expiry.ttl {
+            Some(ttl) => expiry.expiry_manager.register(
+                expiry.destination.clone(),
+                &self.database_name,
+                jobid,
+                ttl,
+            ),
+            None => expiry
+                .expiry_manager
+                .unregister(&expiry.destination, &self.database_name),
+        };
+
+        if let Err(err) = result {
+            warn!(
+                "Failed to update expiry of database {} - {}",
+                self.database_name, err
+            );
+        }
+    }
+
+    fn write_output(&self, jobid: usize, args: Arguments) -> WorkerResult<()> {
+        let stdout_path: PathBuf = self
+            .job_manager
+            .map_job(jobid, |job| job.stdout_path().into())
+            .map_err(WorkerError::map_job_error)?
+            .ok_or_else(|| WorkerError::new("Job not found"))?;
+
+        append_line(&stdout_path, args)
+    }
+
     fn write_error(&self, jobid: usize, args: Arguments) -> WorkerResult<()> {
         let stderr_path: PathBuf = self
             .job_manager
             .map_job(jobid, |job| job.stderr_path().into())
             .map_err(WorkerError::map_job_error)?
             .ok_or_else(|| WorkerError::new("Job not found"))?;
-        let mut stdout = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(stderr_path)
-            .map_err(WorkerError::io_error)?;
 
-        stdout
-            .write_fmt(format_args!("{}\n", args))
-            .map_err(WorkerError::io_error)?;
+        append_line(&stderr_path, args)
+    }
 
-        Ok(())
+    fn is_aborted(&self, jobid: usize) -> bool {
+        self.job_manager
+            .map_job(jobid, |job| job.status() == &JobStatus::Aborted)
+            .unwrap_or_default()
+            .unwrap_or(true)
     }
 
     fn check_backup_path(&self, jobid: usize, path: &Path) -> WorkerResult<()> {
@@ -405,13 +791,15 @@ impl Worker {
 
     fn execute_download<F>(&self, jobid: usize, callback: F) -> WorkerResult<PathHandle>
     where
-        F: FnOnce() -> HttpClientResult<PathHandle>,
+        F: FnMut() -> HttpClientResult<PathHandle>,
     {
         self.job_manager
             .set_stage(jobid, "Download file")
             .map_err(WorkerError::set_stage_error)?;
 
-        match callback() {
+        match self.with_retry(jobid, RetryStage::Download, callback, |err| {
+            err.is_retryable()
+        }) {
             Ok(path) => Ok(path),
             Err(err) => {
                 self.write_error(jobid, format_args!("{}", err))?;
@@ -441,6 +829,109 @@ impl Worker {
         }
     }
 
+    /// Executes command which may be repeated, command is retried if it failed and server is
+    /// not available. `pg_restore` commands are not retried, partially restored objects can
+    /// not be restored again.
+    fn execute_command<F>(&self, jobid: usize, mut callback: F) -> WorkerResult<CommandStatus>
+    where
+        F: FnMut() -> WorkerResult<CommandStatus>,
+    {
+        if !self.retry.retries(RetryStage::Connection) {
+            return callback();
+        }
+
+        let postgres = PostgreSQL::new(&self.destination, MAINTENANCE_DATABASE);
+        let result = self.with_retry(
+            jobid,
+            RetryStage::Connection,
+            || match callback() {
+                Ok(CommandStatus::Failed) => {
+                    postgres.server_info().map(|_| Ok(CommandStatus::Failed))
+                }
+                status => Ok(status),
+            },
+            DatabaseError::is_connection_error,
+        );
+
+        result.unwrap_or(Ok(CommandStatus::Failed))
+    }
+
+    /// Calls callback until it succeeds, returns not retryable error or retries of stage are
+    /// exhausted. Every retry is reported in job error log.
+    fn with_retry<T, E, F, R>(
+        &self,
+        jobid: usize,
+        stage: RetryStage,
+        mut callback: F,
+        retryable: R,
+    ) -> Result<T, E>
+    where
+        E: Display,
+        F: FnMut() -> Result<T, E>,
+        R: Fn(&E) -> bool,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let err = match callback() {
+                Err(err) if retryable(&err) => err,
+                result => return result,
+            };
+
+            attempt += 1;
+
+            let delay = match self.retry.delay(stage, attempt) {
+                Some(delay) => delay,
+                None => return Err(err),
+            };
+
+            info!(
+                "Job {} {:?} stage failed, retry {} of {} in {} seconds",
+                jobid,
+                stage,
+                attempt,
+                self.retry.attempts(),
+                delay.as_secs()
+            );
+
+            if let Err(write_err) = self.write_error(
+                jobid,
+                format_args!(
+                    "{} - retry {} of {} in {} seconds",
+                    err,
+                    attempt,
+                    self.retry.attempts(),
+                    delay.as_secs()
+                ),
+            ) {
+                warn!("Failed to write job {} log - {}", jobid, write_err);
+            }
+
+            if !self.wait_retry(jobid, delay) {
+                return Err(err);
+            }
+        }
+    }
+
+    /// Sleeps before retry, returns `false` if job was aborted meanwhile.
+    fn wait_retry(&self, jobid: usize, delay: Duration) -> bool {
+        let deadline = Instant::now() + delay;
+
+        loop {
+            let now = Instant::now();
+
+            if self.is_aborted(jobid) {
+                return false;
+            }
+
+            if now >= deadline {
+                return true;
+            }
+
+            thread::sleep((deadline - now).min(Duration::from_secs(1)));
+        }
+    }
+
     fn set_aborted(&self, jobid: usize) -> WorkerResult<()> {
         self.job_manager
             .set_aborted(jobid)
@@ -467,6 +958,19 @@ impl Worker {
     }
 }
 
+fn append_line(path: &Path, args: Arguments) -> WorkerResult<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(WorkerError::io_error)?;
+
+    file.write_fmt(format_args!("{}\n", args))
+        .map_err(WorkerError::io_error)?;
+
+    Ok(())
+}
+
 impl WorkerSettings for Worker {
     fn createdb_path(&self) -> &str {
         self.config.commands().createdb_path()
@@ -480,6 +984,10 @@ impl WorkerSettings for Worker {
         self.config.commands().pgrestore_path()
     }
 
+    fn vacuumdb_path(&self) -> Option<&String> {
+        self.config.commands().vacuumdb_path()
+    }
+
     fn restore_jobs(&self) -> usize {
         self.config.restore_jobs()
     }
@@ -508,7 +1016,23 @@ impl WorkerSettings for Worker {
         &self.database_name
     }
 
-    fn ignore_errors(&self) -> bool {
-        self.ignore_errors
+    fn keep_owner(&self) -> bool {
+        self.destination.keep_owner()
+    }
+
+    fn keep_privileges(&self) -> bool {
+        self.destination.keep_privileges()
+    }
+
+    fn ssl_mode(&self) -> SslMode {
+        self.destination.ssl_mode()
+    }
+
+    fn root_certificate(&self) -> Option<&String> {
+        self.destination.root_certificate()
+    }
+
+    fn client_certificate(&self) -> Option<&ClientCertificate> {
+        self.destination.client_certificate()
     }
 }
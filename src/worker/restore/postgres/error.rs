@@ -1,15 +1,19 @@
+use native_tls::Error as TlsError;
 use postgres::Error as PgError;
 use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::io::Error as IoError;
 
 pub type DatabaseResult<T> = Result<T, DatabaseError>;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum DatabaseError {
     ConnectionError { message: String },
     QueryExecutionError { message: String },
+    TlsError { message: String },
 }
 
 impl DatabaseError {
@@ -26,6 +30,25 @@ impl DatabaseError {
             message: format!("{}", error),
         }
     }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn tls_error(error: TlsError) -> DatabaseError {
+        DatabaseError::TlsError {
+            message: format!("TLS error - {}", error),
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn io_error(error: IoError) -> DatabaseError {
+        DatabaseError::TlsError {
+            message: format!("Failed to read certificate - {}", error),
+        }
+    }
+
+    /// Checks if error may be transient, invalid TLS settings are not retried.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, DatabaseError::ConnectionError { .. })
+    }
 }
 
 impl Error for DatabaseError {}
@@ -35,6 +58,7 @@ impl Display for DatabaseError {
         match self {
             DatabaseError::ConnectionError { message } => write!(f, "{}", message),
             DatabaseError::QueryExecutionError { message } => write!(f, "{}", message),
+            DatabaseError::TlsError { message } => write!(f, "{}", message),
         }
     }
 }
@@ -1,47 +1,216 @@
 mod error;
+mod ownership;
 
-use postgres::config::SslMode;
+use native_tls::Certificate;
+use native_tls::Identity;
+use native_tls::TlsConnector;
+use postgres::config::SslMode as PgSslMode;
 use postgres::Client;
+use postgres::Config;
 use postgres::NoTls;
+use postgres_native_tls::MakeTlsConnector;
 
 pub use self::error::DatabaseError;
 pub use self::error::DatabaseResult;
+pub use self::ownership::Scope;
 
+use self::ownership::Relation;
+use self::ownership::RelationKind;
+use super::masking::Column;
 use super::TableDescription;
+use crate::config::ClientCertificate;
+use crate::config::Destination;
+use crate::config::Grant;
+use crate::config::SslMode;
 use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Database used for queries which do not depend on restored database.
+pub const MAINTENANCE_DATABASE: &str = "postgres";
+
+/// Connection and statement timeout of destination checks.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
-#[derive(Debug)]
 pub struct PostgreSQL {
     server: String,
     port: u16,
     user: String,
     password: String,
     database: String,
+    ssl_mode: SslMode,
+    root_certificate: Option<String>,
+    client_certificate: Option<ClientCertificate>,
 }
 
 impl PostgreSQL {
-    pub fn new(server: &str, port: u16, user: &str, password: &str, database: &str) -> PostgreSQL {
+    pub fn new(destination: &Destination, database: &str) -> PostgreSQL {
         PostgreSQL {
-            server: server.into(),
-            port,
-            user: user.into(),
-            password: password.into(),
+            server: destination.host().into(),
+            port: destination.port(),
+            user: destination.role().into(),
+            password: destination.password().into(),
             database: database.into(),
+            ssl_mode: destination.ssl_mode(),
+            root_certificate: destination.root_certificate().cloned(),
+            client_certificate: destination.client_certificate().cloned(),
         }
     }
 
+    /// Returns server version and capabilities of connected role.
+    pub fn server_info(&self) -> DatabaseResult<ServerInfo> {
+        let mut config = self.config();
+        let options = format!("-c statement_timeout={}", CHECK_TIMEOUT.as_millis());
+        let mut connection =
+            self.connect_with(config.connect_timeout(CHECK_TIMEOUT).options(&options))?;
+        let row = connection
+            .query_one(
+                "select current_setting('server_version'), r.rolcreatedb or r.rolsuper, \
+                 case when r.rolsuper or pg_has_role('pg_read_all_settings', 'member') \
+                 then current_setting('data_directory') end \
+                 from pg_roles r where r.rolname = current_user",
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(ServerInfo {
+            version: row.get(0),
+            can_create_database: row.get(1),
+            data_directory: row.get(2),
+        })
+    }
+
+    /// Returns databases of server except templates. Size is known only for databases
+    /// which role can connect to, creation time only if role can read data directory files.
+    pub fn databases(&self) -> DatabaseResult<Vec<DatabaseDescription>> {
+        let mut connection = self.connect()?;
+        // Function privileges are checked even if function is not called, so query reading
+        // files is used only if role is allowed to execute it.
+        let read_files: bool = connection
+            .query_one(
+                "select has_function_privilege('pg_stat_file(text, boolean)', 'execute')",
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?
+            .get(0);
+        let created = if read_files {
+            "extract(epoch from (pg_stat_file('base/' || d.oid || '/PG_VERSION', true))\
+             .modification)::bigint"
+        } else {
+            "null::bigint"
+        };
+        let rows = connection
+            .query(
+                format!(
+                    "select d.datname::text, pg_get_userbyid(d.datdba)::text, \
+                     case when has_database_privilege(d.oid, 'connect') \
+                     then pg_database_size(d.oid) end, {}, \
+                     (select count(*) from pg_stat_activity a where a.datid = d.oid) \
+                     from pg_database d where not d.datistemplate \
+                     order by d.datname",
+                    created
+                )
+                .as_str(),
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DatabaseDescription {
+                name: row.get(0),
+                owner: row.get(1),
+                size: row.get(2),
+                created: row.get(3),
+                connections: row.get(4),
+            })
+            .collect())
+    }
+
+    pub fn drop_database(&self, name: &str) -> DatabaseResult<()> {
+        let mut connection = self.connect()?;
+
+        debug!("Drop database {}", name);
+
+        connection
+            .batch_execute(&format!(
+                "drop database if exists {}",
+                quote_identifier(name)
+            ))
+            .map_err(DatabaseError::query_execution_error)
+    }
+
+    /// Blocks new connections to database and terminates connected sessions, returns
+    /// descriptions of terminated sessions. Does nothing if database does not exist.
+    pub fn terminate_connections(&self, name: &str) -> DatabaseResult<Vec<String>> {
+        let mut connection = self.connect()?;
+        let exists = connection
+            .query_opt("select 1 from pg_database where datname = $1", &[&name])
+            .map_err(DatabaseError::query_execution_error)?
+            .is_some();
+
+        if !exists {
+            return Ok(Vec::new());
+        }
+
+        connection
+            .batch_execute(&format!(
+                "alter database {} allow_connections false",
+                quote_identifier(name)
+            ))
+            .map_err(DatabaseError::query_execution_error)?;
+
+        let rows = connection
+            .query(
+                "select pid, format('pid %s, user %s, application %s, client %s', pid, \
+                 usename, application_name, coalesce(client_addr::text, 'local')) \
+                 from pg_stat_activity where datname = $1 and pid <> pg_backend_pid()",
+                &[&name],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+        let mut result = Vec::new();
+
+        for row in rows {
+            let pid: i32 = row.get(0);
+            let terminated: bool = connection
+                .query_one("select pg_terminate_backend($1)", &[&pid])
+                .map_err(DatabaseError::query_execution_error)?
+                .get(0);
+
+            if terminated {
+                result.push(row.get(1));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Allows connections to database blocked by `terminate_connections`.
+    pub fn allow_connections(&self, name: &str) -> DatabaseResult<()> {
+        let mut connection = self.connect()?;
+
+        connection
+            .batch_execute(&format!(
+                "alter database {} allow_connections true",
+                quote_identifier(name)
+            ))
+            .map_err(DatabaseError::query_execution_error)
+    }
+
     pub fn drop_schemas(&self, schemas: &HashSet<String>) -> DatabaseResult<()> {
         let mut connection = self.connect()?;
 
         for schema in schemas {
             debug!("Drop schema {}", schema);
 
-            // Escape double quotes from schema name
-            let schema = schema.replace('"', "\"\'");
-
             let _rows_updated = connection
                 .execute(
-                    &format!("drop schema if exists \"{}\" cascade", schema),
+                    &format!("drop schema if exists {} cascade", quote_identifier(schema)),
                     &[],
                 )
                 .map_err(DatabaseError::query_execution_error)?;
@@ -56,11 +225,11 @@ impl PostgreSQL {
         for schema in schemas {
             debug!("Create schema: {}", schema);
 
-            // Escape double quotes from schema name
-            let schema = schema.replace('"', "\"\'");
-
             let _rows_updated = connection
-                .execute(&format!("create schema if not exists \"{}\"", schema), &[])
+                .execute(
+                    &format!("create schema if not exists {}", quote_identifier(schema)),
+                    &[],
+                )
                 .map_err(DatabaseError::query_execution_error)?;
         }
 
@@ -76,15 +245,12 @@ impl PostgreSQL {
 
             debug!("Create table: {}.{}", schema_name, table_name);
 
-            // Escape double quotes from schema and table names
-            let schema_name = schema_name.replace('"', "\"\'");
-            let table_name = table_name.replace('"', "\"\'");
-
             let _rows_updated = connection
                 .execute(
                     &format!(
-                        "drop table if exists \"{}\".\"{}\"",
-                        schema_name, table_name
+                        "drop table if exists {}.{}",
+                        quote_identifier(schema_name),
+                        quote_identifier(table_name)
                     ),
                     &[],
                 )
@@ -94,15 +260,400 @@ impl PostgreSQL {
         Ok(())
     }
 
+    /// Returns columns of tables in user schemas with their types. Generated columns and
+    /// identity columns generated always can not be updated.
+    pub fn columns(&self) -> DatabaseResult<Vec<Column>> {
+        let mut connection = self.connect()?;
+        let rows = connection
+            .query(
+                "select c.table_schema::text, c.table_name::text, c.column_name::text, \
+                 format_type(a.atttypid, a.atttypmod), ty.typcategory = 'S', \
+                 c.is_generated <> 'ALWAYS' and coalesce(c.identity_generation, '') <> 'ALWAYS' \
+                 from information_schema.columns c \
+                 join information_schema.tables t \
+                 on t.table_schema = c.table_schema and t.table_name = c.table_name \
+                 join pg_namespace n on n.nspname = c.table_schema \
+                 join pg_class cl on cl.relnamespace = n.oid and cl.relname = c.table_name \
+                 join pg_attribute a on a.attrelid = cl.oid and a.attname = c.column_name \
+                 join pg_type ty on ty.oid = a.atttypid \
+                 where t.table_type = 'BASE TABLE' \
+                 and c.table_schema not in ('pg_catalog', 'information_schema') \
+                 and c.table_schema not like 'pg\\_toast%' \
+                 order by c.table_schema, c.table_name, c.ordinal_position",
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                Column::new(
+                    row.get(0),
+                    row.get(1),
+                    row.get(2),
+                    row.get(3),
+                    row.get(4),
+                    row.get(5),
+                )
+            })
+            .collect())
+    }
+
+    /// Returns tables in user schemas.
+    pub fn tables(&self) -> DatabaseResult<Vec<TableDescription>> {
+        let mut connection = self.connect()?;
+        let rows = connection
+            .query(
+                "select table_schema::text, table_name::text \
+                 from information_schema.tables \
+                 where table_type = 'BASE TABLE' \
+                 and table_schema not in ('pg_catalog', 'information_schema') \
+                 order by table_schema, table_name",
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TableDescription::new(row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Returns user schemas.
+    pub fn schemas(&self) -> DatabaseResult<Vec<String>> {
+        let mut connection = self.connect()?;
+        let rows = connection
+            .query(
+                "select nspname::text from pg_namespace \
+                 where nspname not like 'pg\\_%' and nspname <> 'information_schema' \
+                 order by nspname",
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    /// Changes owner of objects in scope and grants privileges to roles in single transaction.
+    /// Sequences owned by table columns and extension objects follow their owners.
+    pub fn apply_ownership(
+        &self,
+        owner: Option<&str>,
+        grants: &[Grant],
+        scope: Scope,
+    ) -> DatabaseResult<usize> {
+        let relations = if owner.is_some() {
+            self.relations()?
+        } else {
+            Vec::new()
+        };
+        let statements = ownership::statements(owner, grants, scope, &relations);
+
+        self.execute_transaction(&statements)?;
+
+        Ok(statements.len())
+    }
+
+    fn relations(&self) -> DatabaseResult<Vec<Relation>> {
+        let mut connection = self.connect()?;
+        let rows = connection
+            .query(
+                "select n.nspname::text, c.relname::text, c.relkind::text, ''::text \
+                 from pg_class c join pg_namespace n on n.oid = c.relnamespace \
+                 where c.relkind in ('r', 'p', 'v', 'm', 'S', 'f') \
+                 and not exists (select 1 from pg_depend d \
+                 where d.classid = 'pg_class'::regclass and d.objid = c.oid \
+                 and d.deptype in ('a', 'i', 'e')) \
+                 union all \
+                 select n.nspname::text, p.proname::text, 'routine', \
+                 pg_get_function_identity_arguments(p.oid) \
+                 from pg_proc p join pg_namespace n on n.oid = p.pronamespace \
+                 where p.prokind in ('f', 'p') \
+                 and not exists (select 1 from pg_depend d \
+                 where d.classid = 'pg_proc'::regclass and d.objid = p.oid \
+                 and d.deptype = 'e') \
+                 order by 1, 2",
+                &[],
+            )
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                RelationKind::parse(row.get(2), row.get(3))
+                    .map(|kind| Relation::new(row.get(0), row.get(1), kind))
+            })
+            .collect())
+    }
+
+    /// Updates statistics of given tables or whole database, optionally vacuuming tables.
+    pub fn analyze(&self, tables: Option<&[TableDescription]>, vacuum: bool) -> DatabaseResult<()> {
+        let mut connection = self.connect()?;
+        let mut statement = if vacuum {
+            String::from("vacuum analyze")
+        } else {
+            String::from("analyze")
+        };
+
+        if let Some(tables) = tables {
+            let names: Vec<_> = tables
+                .iter()
+                .map(|table| {
+                    format!(
+                        "{}.{}",
+                        quote_identifier(table.schema()),
+                        quote_identifier(table.name())
+                    )
+                })
+                .collect();
+
+            statement.push(' ');
+            statement.push_str(&names.join(", "));
+        }
+
+        debug!("Execute {}", statement);
+
+        connection
+            .batch_execute(&statement)
+            .map_err(DatabaseError::query_execution_error)
+    }
+
+    /// Executes statements in single transaction, returns number of rows modified by every
+    /// statement.
+    pub fn execute_transaction<S>(&self, statements: &[S]) -> DatabaseResult<Vec<u64>>
+    where
+        S: AsRef<str>,
+    {
+        let mut connection = self.connect()?;
+        let mut transaction = connection
+            .transaction()
+            .map_err(DatabaseError::query_execution_error)?;
+        let mut result = Vec::with_capacity(statements.len());
+
+        for statement in statements {
+            debug!("Execute {}", statement.as_ref());
+
+            let rows_updated = transaction
+                .execute(statement.as_ref(), &[])
+                .map_err(DatabaseError::query_execution_error)?;
+
+            result.push(rows_updated);
+        }
+
+        transaction
+            .commit()
+            .map_err(DatabaseError::query_execution_error)?;
+
+        Ok(result)
+    }
+
+    /// Executes script of one or more statements in single implicit transaction. Server
+    /// notices are appended to `notices` even if script fails.
+    pub fn execute_script(&self, script: &str, notices: &mut Vec<String>) -> DatabaseResult<()> {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sender = received.clone();
+        let mut config = self.config();
+
+        config.notice_callback(move |notice| {
+            if let Ok(mut received) = sender.lock() {
+                received.push(format!("{}: {}", notice.severity(), notice.message()));
+            }
+        });
+
+        let result = self.connect_with(&config).and_then(|mut connection| {
+            connection
+                .batch_execute(script)
+                .map_err(DatabaseError::query_execution_error)
+        });
+
+        if let Ok(mut received) = received.lock() {
+            notices.append(&mut received);
+        }
+
+        result
+    }
+
     fn connect(&self) -> DatabaseResult<Client> {
-        Client::configure()
-            .ssl_mode(SslMode::Disable)
+        self.connect_with(&self.config())
+    }
+
+    /// Connects without TLS if SSL is disabled, certificates are read only if TLS is used.
+    fn connect_with(&self, config: &Config) -> DatabaseResult<Client> {
+        match self.ssl_mode {
+            SslMode::Disable => config.connect(NoTls),
+            _ => config.connect(self.tls()?),
+        }
+        .map_err(DatabaseError::connection_error)
+    }
+
+    fn config(&self) -> Config {
+        let mut config = Client::configure();
+
+        config
+            .ssl_mode(pg_ssl_mode(self.ssl_mode))
             .host(&self.server)
             .port(self.port)
             .user(&self.user)
             .password(&self.password)
-            .dbname(&self.database)
-            .connect(NoTls)
-            .map_err(DatabaseError::connection_error)
+            .dbname(&self.database);
+
+        config
+    }
+
+    /// Creates TLS connector with libpq rules: server certificate is verified only if root
+    /// certificate is given, host name is verified only in `VerifyFull` mode.
+    fn tls(&self) -> DatabaseResult<MakeTlsConnector> {
+        let mut builder = TlsConnector::builder();
+
+        if let Some(ref root_certificate) = self.root_certificate {
+            let buffer = fs::read(root_certificate).map_err(DatabaseError::io_error)?;
+            let certificate = Certificate::from_pem(&buffer).map_err(DatabaseError::tls_error)?;
+
+            builder
+                .disable_built_in_roots(true)
+                .add_root_certificate(certificate);
+        } else {
+            builder.danger_accept_invalid_certs(true);
+        }
+
+        if self.ssl_mode != SslMode::VerifyFull {
+            builder.danger_accept_invalid_hostnames(true);
+        }
+
+        // Only PKCS#8 keys are accepted, other formats are rejected by configuration validation.
+        if let Some(ref client_certificate) = self.client_certificate {
+            let certificate =
+                fs::read(client_certificate.certificate()).map_err(DatabaseError::io_error)?;
+            let key = fs::read(client_certificate.key()).map_err(DatabaseError::io_error)?;
+            let identity =
+                Identity::from_pkcs8(&certificate, &key).map_err(DatabaseError::tls_error)?;
+
+            builder.identity(identity);
+        }
+
+        let connector = builder.build().map_err(DatabaseError::tls_error)?;
+
+        Ok(MakeTlsConnector::new(connector))
+    }
+}
+
+impl Debug for PostgreSQL {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("PostgreSQL")
+            .field("server", &self.server)
+            .field("port", &self.port)
+            .field("user", &self.user)
+            .field("password", &"***")
+            .field("database", &self.database)
+            .field("ssl_mode", &self.ssl_mode)
+            .field("root_certificate", &self.root_certificate)
+            .field("client_certificate", &self.client_certificate)
+            .finish()
+    }
+}
+
+/// Returns SSL mode of connection, verification modes require TLS and are checked by
+/// connector.
+fn pg_ssl_mode(ssl_mode: SslMode) -> PgSslMode {
+    match ssl_mode {
+        SslMode::Disable => PgSslMode::Disable,
+        SslMode::Prefer => PgSslMode::Prefer,
+        SslMode::Require | SslMode::VerifyCa | SslMode::VerifyFull => PgSslMode::Require,
+    }
+}
+
+/// Quotes SQL identifier, double quotes in identifier are doubled.
+pub fn quote_identifier(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    version: String,
+    can_create_database: bool,
+    data_directory: Option<String>,
+}
+
+impl ServerInfo {
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn can_create_database(&self) -> bool {
+        self.can_create_database
+    }
+
+    /// Data directory on server, available only to roles allowed to read all settings.
+    pub fn data_directory(&self) -> Option<&String> {
+        self.data_directory.as_ref()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseDescription {
+    name: String,
+    owner: String,
+    size: Option<i64>,
+    created: Option<i64>,
+    connections: i64,
+}
+
+impl DatabaseDescription {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn size(&self) -> Option<i64> {
+        self.size
+    }
+
+    /// Creation time as unix timestamp, approximated by modification time of `PG_VERSION`.
+    pub fn created(&self) -> Option<i64> {
+        self.created
+    }
+
+    pub fn connections(&self) -> i64 {
+        self.connections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pg_ssl_mode;
+    use super::quote_identifier;
+    use super::PostgreSQL;
+    use crate::config::Destination;
+    use crate::config::SslMode;
+    use postgres::config::SslMode as PgSslMode;
+
+    #[test]
+    fn pg_ssl_mode_shold_require_tls_for_verification() {
+        assert_eq!(PgSslMode::Disable, pg_ssl_mode(SslMode::Disable));
+        assert_eq!(PgSslMode::Prefer, pg_ssl_mode(SslMode::Prefer));
+        assert_eq!(PgSslMode::Require, pg_ssl_mode(SslMode::Require));
+        assert_eq!(PgSslMode::Require, pg_ssl_mode(SslMode::VerifyCa));
+        assert_eq!(PgSslMode::Require, pg_ssl_mode(SslMode::VerifyFull));
+    }
+
+    #[test]
+    fn quote_identifier_shold_double_quotes() {
+        assert_eq!("\"users\"", quote_identifier("users"));
+        assert_eq!("\"Sales.2020\"", quote_identifier("Sales.2020"));
+        assert_eq!("\"a\"\"b\"\"\"", quote_identifier("a\"b\""));
+    }
+
+    #[test]
+    fn debug_shold_not_show_password() {
+        let destination: Destination =
+            serde_yaml::from_str("{host: localhost, port: 5432, role: postgres, password: hidden}")
+                .unwrap();
+        let debug = format!("{:?}", PostgreSQL::new(&destination, "qa"));
+
+        assert!(debug.contains("localhost"));
+        assert!(!debug.contains("hidden"));
     }
 }
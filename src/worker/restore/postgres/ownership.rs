@@ -0,0 +1,251 @@
+use super::quote_identifier;
+use super::TableDescription;
+use crate::config::Access;
+use crate::config::Grant;
+use std::collections::BTreeSet;
+
+/// Database object which may change owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relation {
+    schema: String,
+    name: String,
+    kind: RelationKind,
+}
+
+impl Relation {
+    pub fn new(schema: &str, name: &str, kind: RelationKind) -> Relation {
+        Relation {
+            schema: schema.into(),
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelationKind {
+    Table,
+    View,
+    MaterializedView,
+    Sequence,
+    ForeignTable,
+    Routine { arguments: String },
+}
+
+impl RelationKind {
+    /// Parses `pg_class.relkind`, routines are reported as `routine`.
+    pub fn parse(kind: &str, arguments: &str) -> Option<RelationKind> {
+        match kind {
+            "r" | "p" => Some(RelationKind::Table),
+            "v" => Some(RelationKind::View),
+            "m" => Some(RelationKind::MaterializedView),
+            "S" => Some(RelationKind::Sequence),
+            "f" => Some(RelationKind::ForeignTable),
+            "routine" => Some(RelationKind::Routine {
+                arguments: arguments.into(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Objects affected by ownership change and grants: whole schemas and separate tables.
+#[derive(Debug, Clone, Copy)]
+pub struct Scope<'a> {
+    schemas: &'a [String],
+    tables: &'a [TableDescription],
+}
+
+impl<'a> Scope<'a> {
+    pub fn new(schemas: &'a [String], tables: &'a [TableDescription]) -> Scope<'a> {
+        Scope { schemas, tables }
+    }
+
+    fn contains(&self, relation: &Relation) -> bool {
+        self.schemas.contains(&relation.schema)
+            || (relation.kind == RelationKind::Table
+                && self
+                    .tables
+                    .contains(&TableDescription::new(&relation.schema, &relation.name)))
+    }
+}
+
+/// Builds statements changing owner of objects in scope and granting privileges to roles.
+pub fn statements(
+    owner: Option<&str>,
+    grants: &[Grant],
+    scope: Scope,
+    relations: &[Relation],
+) -> Vec<String> {
+    let mut result = Vec::new();
+
+    if let Some(owner) = owner {
+        let owner = quote_identifier(owner);
+
+        for schema in scope.schemas {
+            result.push(format!(
+                "alter schema {} owner to {}",
+                quote_identifier(schema),
+                owner
+            ));
+        }
+
+        for relation in relations.iter().filter(|relation| scope.contains(relation)) {
+            let name = format!(
+                "{}.{}",
+                quote_identifier(&relation.schema),
+                quote_identifier(&relation.name)
+            );
+            let statement = match relation.kind {
+                RelationKind::Table => format!("alter table {} owner to {}", name, owner),
+                RelationKind::View => format!("alter view {} owner to {}", name, owner),
+                RelationKind::MaterializedView => {
+                    format!("alter materialized view {} owner to {}", name, owner)
+                }
+                RelationKind::Sequence => format!("alter sequence {} owner to {}", name, owner),
+                RelationKind::ForeignTable => {
+                    format!("alter foreign table {} owner to {}", name, owner)
+                }
+                RelationKind::Routine { ref arguments } => {
+                    format!("alter routine {}({}) owner to {}", name, arguments, owner)
+                }
+            };
+
+            result.push(statement);
+        }
+    }
+
+    let table_schemas: BTreeSet<_> = scope
+        .schemas
+        .iter()
+        .map(String::as_str)
+        .chain(scope.tables.iter().map(TableDescription::schema))
+        .collect();
+
+    for grant in grants {
+        let role = quote_identifier(grant.role());
+        let (schema, tables, sequences) = match grant.access() {
+            Access::ReadOnly => ("usage", "select", "select"),
+            Access::ReadWrite => (
+                "usage",
+                "select, insert, update, delete",
+                "usage, select, update",
+            ),
+            Access::All => ("all", "all", "all"),
+        };
+
+        for name in &table_schemas {
+            result.push(format!(
+                "grant {} on schema {} to {}",
+                schema,
+                quote_identifier(name),
+                role
+            ));
+        }
+
+        for name in scope.schemas {
+            let name = quote_identifier(name);
+
+            result.push(format!(
+                "grant {} on all tables in schema {} to {}",
+                tables, name, role
+            ));
+            result.push(format!(
+                "grant {} on all sequences in schema {} to {}",
+                sequences, name, role
+            ));
+
+            if grant.access() == Access::All {
+                result.push(format!(
+                    "grant all on all routines in schema {} to {}",
+                    name, role
+                ));
+            }
+        }
+
+        for table in scope.tables {
+            result.push(format!(
+                "grant {} on table {}.{} to {}",
+                tables,
+                quote_identifier(table.schema()),
+                quote_identifier(table.name()),
+                role
+            ));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::statements;
+    use super::Relation;
+    use super::RelationKind;
+    use super::Scope;
+    use crate::config::Grant;
+    use crate::worker::restore::TableDescription;
+
+    fn relations() -> Vec<Relation> {
+        vec![
+            Relation::new("app", "users", RelationKind::Table),
+            Relation::new("app", "user_ids", RelationKind::Sequence),
+            Relation::new(
+                "app",
+                "login",
+                RelationKind::Routine {
+                    arguments: "name text".into(),
+                },
+            ),
+            Relation::new("public", "orders", RelationKind::Table),
+            Relation::new("public", "totals", RelationKind::View),
+        ]
+    }
+
+    #[test]
+    fn statements_shold_change_owner_of_schemas() {
+        let schemas = vec!["app".to_string()];
+        let statements = statements(Some("owner"), &[], Scope::new(&schemas, &[]), &relations());
+
+        assert_eq!(
+            vec![
+                "alter schema \"app\" owner to \"owner\"",
+                "alter table \"app\".\"users\" owner to \"owner\"",
+                "alter sequence \"app\".\"user_ids\" owner to \"owner\"",
+                "alter routine \"app\".\"login\"(name text) owner to \"owner\"",
+            ],
+            statements
+        );
+    }
+
+    #[test]
+    fn statements_shold_change_owner_of_tables() {
+        let tables = vec![TableDescription::new("public", "orders")];
+        let statements = statements(Some("owner"), &[], Scope::new(&[], &tables), &relations());
+
+        assert_eq!(
+            vec!["alter table \"public\".\"orders\" owner to \"owner\""],
+            statements
+        );
+    }
+
+    #[test]
+    fn statements_shold_grant_access() {
+        let grants: Vec<Grant> =
+            serde_yaml::from_str("[{role: reader, access: ReadOnly}]").unwrap();
+        let schemas = vec!["app".to_string()];
+        let tables = vec![TableDescription::new("public", "orders")];
+        let statements = statements(None, &grants, Scope::new(&schemas, &tables), &relations());
+
+        assert_eq!(
+            vec![
+                "grant usage on schema \"app\" to \"reader\"",
+                "grant usage on schema \"public\" to \"reader\"",
+                "grant select on all tables in schema \"app\" to \"reader\"",
+                "grant select on all sequences in schema \"app\" to \"reader\"",
+                "grant select on table \"public\".\"orders\" to \"reader\"",
+            ],
+            statements
+        );
+    }
+}
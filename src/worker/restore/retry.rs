@@ -0,0 +1,105 @@
+use crate::config::Retry;
+use crate::config::RetryStage;
+use std::time::Duration;
+
+/// Retry settings of restore worker, no retries if not configured.
+#[derive(Debug, Clone, Default)]
+pub struct RetryPolicy {
+    attempts: usize,
+    delay: Duration,
+    max_delay: Option<Duration>,
+    stages: Vec<RetryStage>,
+}
+
+impl RetryPolicy {
+    pub fn new(retry: Option<&Retry>) -> RetryPolicy {
+        match retry {
+            Some(retry) => RetryPolicy {
+                attempts: retry.attempts(),
+                delay: Duration::from_secs(retry.delay()),
+                max_delay: retry.max_delay().map(Duration::from_secs),
+                stages: retry.stages().into(),
+            },
+            None => RetryPolicy::default(),
+        }
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Checks if failures of stage are retried.
+    pub fn retries(&self, stage: RetryStage) -> bool {
+        self.attempts > 0 && self.stages.contains(&stage)
+    }
+
+    /// Returns delay before retry number `attempt` (starting from 1) of stage or `None` if
+    /// stage should not be retried any more.
+    pub fn delay(&self, stage: RetryStage, attempt: usize) -> Option<Duration> {
+        if attempt == 0 || attempt > self.attempts || !self.stages.contains(&stage) {
+            return None;
+        }
+
+        let factor = 1u32.checked_shl(attempt as u32 - 1).unwrap_or(u32::MAX);
+        let delay = self.delay.checked_mul(factor).unwrap_or(Duration::MAX);
+
+        match self.max_delay {
+            Some(max_delay) => Some(delay.min(max_delay)),
+            None => Some(delay),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use crate::config::RetryStage;
+    use std::time::Duration;
+
+    fn policy(max_delay: Option<u64>) -> RetryPolicy {
+        RetryPolicy {
+            attempts: 3,
+            delay: Duration::from_secs(10),
+            max_delay: max_delay.map(Duration::from_secs),
+            stages: vec![RetryStage::Download],
+        }
+    }
+
+    #[test]
+    fn delay_shold_double_after_every_attempt() {
+        let policy = policy(None);
+
+        assert_eq!(
+            Some(Duration::from_secs(10)),
+            policy.delay(RetryStage::Download, 1)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(20)),
+            policy.delay(RetryStage::Download, 2)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(40)),
+            policy.delay(RetryStage::Download, 3)
+        );
+        assert_eq!(None, policy.delay(RetryStage::Download, 4));
+    }
+
+    #[test]
+    fn delay_shold_be_limited() {
+        let policy = policy(Some(15));
+
+        assert_eq!(
+            Some(Duration::from_secs(15)),
+            policy.delay(RetryStage::Download, 3)
+        );
+    }
+
+    #[test]
+    fn delay_shold_skip_other_stages() {
+        assert_eq!(None, policy(None).delay(RetryStage::Connection, 1));
+        assert_eq!(None, RetryPolicy::default().delay(RetryStage::Download, 1));
+        assert!(policy(None).retries(RetryStage::Download));
+        assert!(!policy(None).retries(RetryStage::Connection));
+        assert!(!RetryPolicy::default().retries(RetryStage::Download));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::config::Directory;
+use glob::MatchOptions;
+use glob::Pattern;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// `*` matches inside single path component, `**` matches any number of components.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Debug, Clone)]
+pub struct ScanDirectory {
+    path: PathBuf,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    follow_symlinks: bool,
+    recursion_limit: usize,
+    interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl ScanDirectory {
+    pub fn new(directory: &Directory, recursion_limit: usize, interval: u64) -> ScanDirectory {
+        ScanDirectory {
+            path: directory.path().into(),
+            include: compile(directory.include()),
+            exclude: compile(directory.exclude()),
+            follow_symlinks: directory.follow_symlinks(),
+            recursion_limit: directory.recursion_limit().unwrap_or(recursion_limit),
+            interval: Duration::from_secs(directory.interval().unwrap_or(interval)),
+            timeout: directory.timeout().map(Duration::from_secs),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn follow_symlinks(&self) -> bool {
+        self.follow_symlinks
+    }
+
+    pub fn recursion_limit(&self) -> usize {
+        self.recursion_limit
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Maximal duration of single scan, scan stopped and reported as failed when exceeded.
+    /// Timeout is checked between directory entries, blocked file system call is not
+    /// interrupted.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns path relative to directory or `None` if path is outside of directory.
+    pub fn relative<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        path.strip_prefix(&self.path).ok()
+    }
+
+    /// Checks relative path and all its parents against exclude patterns.
+    pub fn is_excluded(&self, relative: &Path) -> bool {
+        relative
+            .ancestors()
+            .filter(|path| !path.as_os_str().is_empty())
+            .any(|path| {
+                self.exclude
+                    .iter()
+                    .any(|pattern| pattern.matches_path_with(path, MATCH_OPTIONS))
+            })
+    }
+
+    /// Checks relative file path against include patterns, all files included if no patterns
+    /// given.
+    pub fn is_included(&self, relative: &Path) -> bool {
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| pattern.matches_path_with(relative, MATCH_OPTIONS))
+    }
+}
+
+fn compile(patterns: &[String]) -> Vec<Pattern> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match Pattern::new(pattern) {
+            Ok(pattern) => Some(pattern),
+            Err(err) => {
+                warn!("Directory pattern {} skipped - {}", pattern, err);
+
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScanDirectory;
+    use crate::config::Directory;
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn directory(settings: &str) -> ScanDirectory {
+        ScanDirectory::new(&serde_yaml::from_str::<Directory>(settings).unwrap(), 5, 60)
+    }
+
+    #[test]
+    fn plain_path_shold_include_everything() {
+        let directory = directory("/backups");
+
+        assert_eq!(Path::new("/backups"), directory.path());
+        assert_eq!(5, directory.recursion_limit());
+        assert_eq!(Duration::from_secs(60), directory.interval());
+        assert_eq!(None, directory.timeout());
+        assert!(directory.follow_symlinks());
+        assert!(!directory.is_excluded(Path::new("archive/old/db.backup")));
+        assert!(directory.is_included(Path::new("archive/old/db.backup")));
+    }
+
+    #[test]
+    fn exclude_shold_skip_subtree() {
+        let directory = directory("{path: /backups, exclude: [archive/old, '**/tmp']}");
+
+        assert!(directory.is_excluded(Path::new("archive/old")));
+        assert!(directory.is_excluded(Path::new("archive/old/db.backup")));
+        assert!(directory.is_excluded(Path::new("daily/tmp/db.backup")));
+        assert!(!directory.is_excluded(Path::new("archive/new/db.backup")));
+    }
+
+    #[test]
+    fn include_shold_match_file_names() {
+        let directory = directory(
+            "{path: /backups, include: ['**/prod_*'], follow_symlinks: false, recursion_limit: 2}",
+        );
+
+        assert_eq!(2, directory.recursion_limit());
+        assert!(!directory.follow_symlinks());
+        assert!(directory.is_included(Path::new("daily/prod_sales.backup")));
+        assert!(!directory.is_included(Path::new("prod/sales.backup")));
+    }
+
+    #[test]
+    fn interval_shold_override_default() {
+        let directory = directory("{path: /mnt/tape, interval: 86400, timeout: 3600}");
+
+        assert_eq!(Duration::from_secs(86400), directory.interval());
+        assert_eq!(Some(Duration::from_secs(3600)), directory.timeout());
+    }
+
+    #[test]
+    fn relative_shold_strip_directory() {
+        let directory = directory("/backups");
+
+        assert_eq!(
+            Some(Path::new("daily/db.backup")),
+            directory.relative(Path::new("/backups/daily/db.backup"))
+        );
+        assert_eq!(None, directory.relative(Path::new("/other/db.backup")));
+    }
+}
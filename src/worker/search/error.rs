@@ -1,4 +1,5 @@
 use crate::pathmanager::PathManagerError;
+use notify::Error as NotifyError;
 use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -7,10 +8,13 @@ use std::io::Error as IoError;
 
 pub type WorkerResult<T> = Result<T, WorkerError>;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum WorkerError {
     IoError { message: String },
     PathManagerError { message: String },
+    WatchError { message: String },
+    TimeoutError { message: String },
 }
 
 impl WorkerError {
@@ -31,6 +35,23 @@ impl WorkerError {
             message: format!("{}", error),
         }
     }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn watch_error(error: NotifyError) -> WorkerError {
+        warn!("Watch error - {}", error);
+
+        WorkerError::WatchError {
+            message: format!("{}", error),
+        }
+    }
+
+    pub fn timeout_error() -> WorkerError {
+        warn!("Scan timeout exceed");
+
+        WorkerError::TimeoutError {
+            message: "Scan timeout exceed".into(),
+        }
+    }
 }
 
 impl Error for WorkerError {}
@@ -40,6 +61,8 @@ impl Display for WorkerError {
         match self {
             WorkerError::IoError { message } => write!(f, "{}", message),
             WorkerError::PathManagerError { message } => write!(f, "{}", message),
+            WorkerError::WatchError { message } => write!(f, "{}", message),
+            WorkerError::TimeoutError { message } => write!(f, "{}", message),
         }
     }
 }
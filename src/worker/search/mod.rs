@@ -1,38 +1,56 @@
+mod directory;
 mod error;
+mod watcher;
 
 pub use self::error::WorkerError;
 pub use self::error::WorkerResult;
 
+use self::directory::ScanDirectory;
 use crate::config::ConfigRef;
+use crate::pathmanager::BackupEntry;
 use crate::pathmanager::PathManagerRef;
+use crate::scanmanager::ScanManagerRef;
 use std::collections::HashSet;
 use std::ffi::OsString;
+use std::fmt::Display;
 use std::fs;
+use std::fs::DirEntry;
+use std::fs::FileType;
+use std::path::Path;
 use std::path::PathBuf;
 use std::thread;
 use std::thread::Builder;
-use std::time::Duration;
+use std::time::Instant;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Worker {
-    interval: u64,
+    watch: bool,
     path_manager: PathManagerRef,
-    directories: Vec<PathBuf>,
+    scan_manager: ScanManagerRef,
+    directories: Vec<ScanDirectory>,
     extensions: HashSet<OsString>,
-    recursion_limit: usize,
 }
 
 const RECURSION_LIMIT: usize = 5;
 
 impl Worker {
     #[allow(clippy::needless_pass_by_value)]
-    fn new(config: ConfigRef, path_manager: PathManagerRef) -> Worker {
+    fn new(
+        config: ConfigRef,
+        path_manager: PathManagerRef,
+        scan_manager: ScanManagerRef,
+    ) -> Worker {
         let interval = config.search_config().interval();
+        let watch = config.search_config().watch();
+        let recursion_limit = config
+            .search_config()
+            .recursion_limit()
+            .unwrap_or(RECURSION_LIMIT);
         let directories = config
             .search_config()
             .directories()
             .iter()
-            .map(|d| d.into())
+            .map(|d| ScanDirectory::new(d, recursion_limit, interval))
             .collect();
         let extensions = config
             .search_config()
@@ -40,17 +58,13 @@ impl Worker {
             .iter()
             .map(|d| d.into())
             .collect();
-        let recursion_limit = config
-            .search_config()
-            .recursion_limit()
-            .unwrap_or(RECURSION_LIMIT);
 
         Worker {
-            interval,
+            watch,
             path_manager,
+            scan_manager,
             directories,
             extensions,
-            recursion_limit,
         }
     }
 
@@ -67,28 +81,126 @@ impl Worker {
             return;
         }
 
+        if self.watch {
+            watcher::start(self.clone());
+        }
+
+        for (index, directory) in self.directories.iter().enumerate() {
+            self.spawn_directory(index, directory.path());
+        }
+    }
+
+    fn spawn_directory(&self, index: usize, directory: &Path) {
+        let worker = self.clone();
+        let path = directory.to_path_buf();
+
+        if let Err(err) = Builder::new()
+            .name(format!("search worker {}", index))
+            .spawn(move || worker.start_directory(&path))
+        {
+            warn!(
+                "Failed to start path worker for {} - {}",
+                directory.display(),
+                err
+            );
+        }
+    }
+
+    /// Periodically scans single directory, waits for rescan request between scans.
+    fn start_directory(&self, directory: &Path) {
+        let root = match self.directories.iter().find(|d| d.path() == directory) {
+            Some(root) => root,
+            None => return,
+        };
+
         loop {
-            info!("Start scanning paths");
+            info!("Start scanning {}", directory.display());
 
-            if let Err(err) = self.path_manager.retain(|path| path.is_file()) {
+            if let Err(err) = self.path_manager.retain(|entry| {
+                !entry.path().starts_with(directory)
+                    || (entry.path().is_file() && self.accepts_file(entry.path()))
+            }) {
                 warn!("Failed to retain old paths - {}", err);
             }
 
-            for directory in &self.directories {
-                debug!("Scanning {}", directory.display());
+            self.scan_root(root);
 
-                if let Err(err) = self.scan_directory(directory, self.recursion_limit) {
-                    warn!("Directory scan error - {}", err);
-                }
+            info!("Scan of {} complete", directory.display());
+
+            if let Err(err) = self.path_manager.save() {
+                warn!("Failed to save paths index - {}", err);
             }
 
-            info!("Scan complete");
+            if let Err(err) = self.scan_manager.wait_rescan(directory, root.interval()) {
+                warn!("Failed to wait rescan request - {}", err);
 
-            thread::sleep(Duration::from_secs(self.interval));
+                thread::sleep(root.interval());
+            }
+
+            match self.scan_manager.stop_removed(directory) {
+                Ok(true) => return self.stop_directory(directory),
+                Ok(false) => {}
+                Err(err) => warn!("Failed to check directory configuration - {}", err),
+            }
         }
     }
 
-    fn scan_directory(&self, path: &PathBuf, recursion_limit: usize) -> WorkerResult<()> {
+    /// Forgets backups of directory removed from configuration.
+    fn stop_directory(&self, directory: &Path) {
+        info!(
+            "Directory {} removed from configuration, scan stopped",
+            directory.display()
+        );
+
+        if let Err(err) = self
+            .path_manager
+            .retain(|entry| !entry.path().starts_with(directory))
+        {
+            warn!("Failed to remove old paths - {}", err);
+        }
+
+        if let Err(err) = self.path_manager.save() {
+            warn!("Failed to save paths index - {}", err);
+        }
+    }
+
+    fn scan_root(&self, root: &ScanDirectory) {
+        let directory = root.path();
+
+        if let Err(err) = self.scan_manager.scan_started(directory) {
+            warn!("Failed to update scan status - {}", err);
+        }
+
+        debug!("Scanning {}", directory.display());
+
+        // Timeout counted after scan slot acquired, time in queue is not limited.
+        let mut report = ScanReport {
+            deadline: root.timeout().map(|timeout| Instant::now() + timeout),
+            ..ScanReport::default()
+        };
+
+        if let Err(err) = self.scan_directory(root, directory, root.recursion_limit(), &mut report)
+        {
+            warn!("Directory scan error - {}", err);
+
+            report.add_error(directory, err);
+        }
+
+        if let Err(err) =
+            self.scan_manager
+                .scan_finished(directory, report.files_found, &report.errors)
+        {
+            warn!("Failed to update scan status - {}", err);
+        }
+    }
+
+    fn scan_directory(
+        &self,
+        root: &ScanDirectory,
+        path: &Path,
+        recursion_limit: usize,
+        report: &mut ScanReport,
+    ) -> WorkerResult<()> {
         if recursion_limit == 0 {
             info!(
                 "Directory {} skipped - Recursion limit exceed",
@@ -98,23 +210,69 @@ impl Worker {
             return Ok(());
         }
 
+        if !report
+            .visited
+            .insert(fs::canonicalize(path).map_err(WorkerError::io_error)?)
+        {
+            info!(
+                "Directory {} skipped - Already visited, possible symlink loop",
+                path.display()
+            );
+
+            return Ok(());
+        }
+
         for entry in fs::read_dir(path).map_err(WorkerError::io_error)? {
-            let path = entry.unwrap().path();
+            if report.is_timed_out() {
+                return Err(WorkerError::timeout_error());
+            }
+
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    warn!("Failed to read entry of {} - {}", path.display(), err);
+
+                    report.add_error(path, err);
+
+                    continue;
+                }
+            };
+            let path = entry.path();
+            let relative = match root.relative(&path) {
+                Some(relative) if !root.is_excluded(relative) => relative,
+                _ => {
+                    debug!("Path {} excluded", path.display());
+
+                    continue;
+                }
+            };
+            let file_type = match entry_type(root, &entry) {
+                Ok(Some(file_type)) => file_type,
+                Ok(None) => continue,
+                Err(err) => {
+                    report.add_error(&path, err);
+
+                    continue;
+                }
+            };
 
-            if path.is_dir() {
-                if let Err(err) = self.scan_directory(&path, recursion_limit - 1) {
-                    warn!("Directory {} skipped - {}", path.display(), err);
+            if file_type.is_dir() {
+                match self.scan_directory(root, &path, recursion_limit - 1, report) {
+                    Ok(()) => {}
+                    Err(err @ WorkerError::TimeoutError { .. }) => return Err(err),
+                    Err(err) => {
+                        warn!("Directory {} skipped - {}", path.display(), err);
+
+                        report.add_error(&path, err);
+                    }
                 }
-            } else if path.is_file() {
-                if let Some(extension) = path.extension() {
-                    if self.extensions.contains(extension) {
-                        if let Err(err) = self
-                            .path_manager
-                            .add_path(&path)
-                            .map_err(WorkerError::add_path_error)
-                        {
-                            warn!("Failed to add path - {}", err);
-                        }
+            } else if file_type.is_file() && root.is_included(relative) && self.is_backup(&path) {
+                match self.add_file(&path) {
+                    Ok(()) => report.files_found += 1,
+                    Err(err) => {
+                        warn!("Failed to add path - {}", err);
+
+                        report.add_error(&path, err);
                     }
                 }
             }
@@ -122,13 +280,118 @@ impl Worker {
 
         Ok(())
     }
+
+    fn is_backup(&self, path: &Path) -> bool {
+        match path.extension() {
+            Some(extension) => self.extensions.contains(extension),
+            None => false,
+        }
+    }
+
+    /// Returns configured directory containing path and path relative to it.
+    fn find_directory<'a>(&self, path: &'a Path) -> Option<(&ScanDirectory, &'a Path)> {
+        self.directories.iter().find_map(|directory| {
+            directory
+                .relative(path)
+                .map(|relative| (directory, relative))
+        })
+    }
+
+    /// Checks whether file would be found by directory scan.
+    fn accepts_file(&self, path: &Path) -> bool {
+        self.directories
+            .iter()
+            .any(|directory| match directory.relative(path) {
+                Some(relative) => {
+                    relative.components().count() <= directory.recursion_limit()
+                        && !directory.is_excluded(relative)
+                        && directory.is_included(relative)
+                        && self.is_backup(path)
+                }
+                None => false,
+            })
+    }
+
+    fn add_file(&self, path: &Path) -> WorkerResult<()> {
+        let previous = self
+            .path_manager
+            .get_entry(path)
+            .map_err(WorkerError::add_path_error)?;
+        let entry = BackupEntry::read(path, previous.as_ref()).map_err(WorkerError::io_error)?;
+
+        self.path_manager
+            .add_entry(entry)
+            .map_err(WorkerError::add_path_error)
+    }
 }
 
-pub fn start(config: ConfigRef, path_manager: PathManagerRef) {
+#[derive(Debug, Default)]
+struct ScanReport {
+    files_found: usize,
+    errors: Vec<String>,
+    /// Canonical paths of scanned directories, used to detect symlink loops.
+    visited: HashSet<PathBuf>,
+    /// Checked before each directory entry, hung `read_dir` or `metadata` call is not limited.
+    deadline: Option<Instant>,
+}
+
+impl ScanReport {
+    fn add_error<E>(&mut self, path: &Path, error: E)
+    where
+        E: Display,
+    {
+        self.errors.push(format!("{} - {}", path.display(), error));
+    }
+
+    fn is_timed_out(&self) -> bool {
+        match self.deadline {
+            Some(deadline) => Instant::now() >= deadline,
+            None => false,
+        }
+    }
+}
+
+/// Returns type of entry, following symlink if allowed. Returns `None` for skipped symlinks.
+fn entry_type(root: &ScanDirectory, entry: &DirEntry) -> WorkerResult<Option<FileType>> {
+    let file_type = entry.file_type().map_err(WorkerError::io_error)?;
+
+    if !file_type.is_symlink() {
+        return Ok(Some(file_type));
+    }
+
+    if !root.follow_symlinks() {
+        debug!("Symlink {} skipped", entry.path().display());
+
+        return Ok(None);
+    }
+
+    let metadata = fs::metadata(entry.path()).map_err(WorkerError::io_error)?;
+
+    Ok(Some(metadata.file_type()))
+}
+
+pub fn start(config: ConfigRef, path_manager: PathManagerRef, scan_manager: ScanManagerRef) {
     if let Err(err) = Builder::new()
         .name("search worker".to_string())
-        .spawn(move || Worker::new(config, path_manager).start())
+        .spawn(move || Worker::new(config, path_manager, scan_manager).start())
     {
         warn!("Failed to start path worker - {}", err);
     }
 }
+
+/// Starts scan of directories added by configuration reload. Added directories are not
+/// watched, changes are found by periodic scans.
+pub fn start_directories(
+    config: ConfigRef,
+    path_manager: PathManagerRef,
+    scan_manager: ScanManagerRef,
+    directories: &[PathBuf],
+) {
+    let worker = Worker::new(config, path_manager, scan_manager);
+
+    for (index, directory) in worker.directories.iter().enumerate() {
+        if directories.iter().any(|path| path == directory.path()) {
+            worker.spawn_directory(index, directory.path());
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::ScanReport;
+use super::Worker;
+use super::WorkerError;
+use super::WorkerResult;
+use notify::event::AccessKind;
+use notify::event::AccessMode;
+use notify::event::ModifyKind;
+use notify::event::RenameMode;
+use notify::Event;
+use notify::EventKind;
+use notify::RecursiveMode;
+use notify::Watcher;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread::Builder;
+
+/// Watches configured directories and updates path manager as soon as backup files appear or
+/// disappear. Periodic scan still required for file systems which do not deliver events.
+pub fn start(worker: Worker) {
+    if let Err(err) = Builder::new()
+        .name("search watcher".to_string())
+        .spawn(move || {
+            if let Err(err) = watch(&worker) {
+                warn!("Path watcher stopped - {}", err);
+            }
+        })
+    {
+        warn!("Failed to start path watcher - {}", err);
+    }
+}
+
+fn watch(worker: &Worker) -> WorkerResult<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(WorkerError::watch_error)?;
+
+    for directory in &worker.directories {
+        let path = directory.path();
+
+        debug!("Watching {}", path.display());
+
+        if let Err(err) = watcher.watch(path, RecursiveMode::Recursive) {
+            warn!("Failed to watch {} - {}", path.display(), err);
+        }
+    }
+
+    for event in receiver {
+        match event {
+            Ok(event) => handle_event(worker, event),
+            Err(err) => warn!("Path watcher error - {}", err),
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_event(worker: &Worker, event: Event) {
+    match event.kind {
+        EventKind::Create(_)
+        | EventKind::Access(AccessKind::Close(AccessMode::Write))
+        | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            for path in &event.paths {
+                add_path(worker, path);
+            }
+        }
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            for path in &event.paths {
+                remove_path(worker, path);
+            }
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            if let [from, to] = event.paths.as_slice() {
+                remove_path(worker, from);
+                add_path(worker, to);
+            }
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => {
+            for path in &event.paths {
+                if path.exists() {
+                    add_path(worker, path);
+                } else {
+                    remove_path(worker, path);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn add_path(worker: &Worker, path: &Path) {
+    let (directory, relative) = match worker.find_directory(path) {
+        Some((directory, relative)) if !directory.is_excluded(relative) => (directory, relative),
+        _ => return,
+    };
+
+    // Watches are not removed on configuration reload, events of removed directories are
+    // ignored.
+    match worker.scan_manager.is_configured(directory.path()) {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(err) => {
+            warn!("Failed to check directory configuration - {}", err);
+
+            return;
+        }
+    }
+
+    let depth = relative.components().count();
+    let is_symlink = path
+        .symlink_metadata()
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false);
+
+    if is_symlink && !directory.follow_symlinks() {
+        return;
+    }
+
+    if path.is_dir() {
+        // Directory moved into watched tree, its content will not produce separate events.
+        if depth < directory.recursion_limit() {
+            debug!("Directory {} appeared", path.display());
+
+            let mut report = ScanReport::default();
+
+            if let Err(err) = worker.scan_directory(
+                directory,
+                path,
+                directory.recursion_limit() - depth,
+                &mut report,
+            ) {
+                warn!("Directory {} skipped - {}", path.display(), err);
+            }
+        }
+    } else if path.is_file() && worker.accepts_file(path) {
+        debug!("File {} appeared", path.display());
+
+        if let Err(err) = worker.add_file(path) {
+            warn!("Failed to add path - {}", err);
+        }
+    }
+}
+
+fn remove_path(worker: &Worker, path: &Path) {
+    debug!("Path {} disappeared", path.display());
+
+    if let Err(err) = worker
+        .path_manager
+        .retain(|entry| !entry.path().starts_with(path))
+    {
+        warn!("Failed to remove path - {}", err);
+    }
+}